@@ -1,4 +1,6 @@
-advent_of_code::solution!(2);
+use advent_of_code::template::Rng;
+
+advent_of_code::solution!(2, 1 => [part_one, part_one_brute_force], 2 => [part_two]; generator = generate);
 
 #[derive(Debug)]
 pub struct IDRange {
@@ -35,6 +37,17 @@ impl IDRange {
         invalid_ids
     }
 
+    /// Checks every ID in the range instead of jumping from one invalid ID to the next.
+    pub fn find_invalid_brute_force(&self) -> Vec<u64> {
+        (self.start..=self.end)
+            .filter(|id| {
+                let s = id.to_string();
+                let (upper, lower) = s.split_at(s.len() / 2);
+                s.len() % 2 == 0 && upper == lower
+            })
+            .collect()
+    }
+
     pub fn find_invalid_v2(&self) -> Vec<u64> {
         let mut invalid_ids = Vec::new();
         for id in self.start..=self.end {
@@ -76,8 +89,16 @@ pub fn part_one(input: &str) -> Option<u64> {
     let res = input
         .split(",")
         .map(|s| IDRange::try_from(s).expect("invalid input"))
-        .map(|r| r.find_invalid())
-        .flatten()
+        .flat_map(|r| r.find_invalid())
+        .sum();
+    Some(res)
+}
+
+pub fn part_one_brute_force(input: &str) -> Option<u64> {
+    let res = input
+        .split(",")
+        .map(|s| IDRange::try_from(s).expect("invalid input"))
+        .flat_map(|r| r.find_invalid_brute_force())
         .sum();
     Some(res)
 }
@@ -86,15 +107,29 @@ pub fn part_two(input: &str) -> Option<u64> {
     let res = input
         .split(",")
         .map(|s| IDRange::try_from(s).expect("invalid input"))
-        .map(|r| r.find_invalid_v2())
-        .flatten()
+        .flat_map(|r| r.find_invalid_v2())
         .sum();
     Some(res)
 }
 
+/// Ranges of up to `size * 20` IDs, with more digits as the size grows.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size as u64;
+    (0..rng.range(1..=size.min(10)))
+        .map(|_| {
+            let digits = rng.range(1..=(1 + size / 10).min(12)) as u32;
+            let start = rng.range(1..=10_u64.pow(digits));
+            let end = start + rng.below(size * 20 + 1);
+            format!("{start}-{end}")
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::property::Differential;
 
     #[test]
     fn test_part_one() {
@@ -102,9 +137,20 @@ mod tests {
         assert_eq!(result, Some(1227775554));
     }
 
+    #[test]
+    fn test_part_one_brute_force() {
+        let result = part_one_brute_force(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(1227775554));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(4174379265));
     }
+
+    #[test]
+    fn test_part_one_against_brute_force() {
+        Differential::new(generate).check(part_one, part_one_brute_force);
+    }
 }
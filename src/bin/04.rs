@@ -1,78 +1,64 @@
 advent_of_code::solution!(4);
 
+use advent_of_code::{
+    grid::{Grid, GridError},
+    template::visualize::{self, Color, Frame},
+};
+
 const PAPER: u8 = b'@';
 const REMOVED: u8 = b'x';
 
-pub struct Grid<const N: usize> {
-    pub data: [[u8; N]; N],
+pub struct Department {
+    pub grid: Grid<u8>,
 }
 
-impl<const N: usize> Grid<N> {
-    pub fn new(data: [[u8; N]; N]) -> Self {
-        Self { data }
-    }
-
-    pub fn neighbors(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
-        let mut result = Vec::new();
-
-        for dr in [-1isize, 0, 1].iter() {
-            for dc in [-1isize, 0, 1].iter() {
-                if *dr == 0 && *dc == 0 {
-                    continue;
-                }
-                let new_row = row as isize + dr;
-                let new_col = col as isize + dc;
-
-                if new_row >= 0 && new_row < N as isize && new_col >= 0 && new_col < N as isize {
-                    result.push((new_row as usize, new_col as usize));
-                }
-            }
-        }
-
-        result
+impl Department {
+    pub fn is_paper(&self, x: usize, y: usize) -> bool {
+        self.grid[(x, y)] == PAPER
     }
 
-    pub fn is_paper(&self, row: usize, col: usize) -> bool {
-        self.data[row][col] == PAPER
-    }
-
-    pub fn accessable(&self, row: usize, col: usize) -> bool {
-        if !self.is_paper(row, col) {
+    pub fn accessable(&self, x: usize, y: usize) -> bool {
+        if !self.is_paper(x, y) {
             return false;
         }
-        let neighbors = self.neighbors(row, col);
-        neighbors
-            .iter()
-            .filter(|(r, c)| self.is_paper(*r, *c))
+        self.grid
+            .neighbors8(x, y)
+            .filter(|(x, y)| self.is_paper(*x, *y))
             .count()
             < 4
     }
 
     pub fn solution(&self) -> u64 {
-        let mut count = 0u64;
-
-        for i in 0..N {
-            for j in 0..N {
-                if self.accessable(i, j) {
-                    count += 1;
-                }
-            }
-        }
+        self.grid
+            .find_all(&PAPER)
+            .filter(|(x, y)| self.accessable(*x, *y))
+            .count() as u64
+    }
 
-        count
+    /// Shows the paper that is left after a round, and the paper it removed.
+    fn frame(&self, removed: u64) -> Frame {
+        let mut frame = Frame::from_fn(self.grid.width(), self.grid.height(), |x, y| {
+            self.grid[(x, y)] as char
+        });
+        frame
+            .color_all(PAPER as char, Color::Gray)
+            .color_all(REMOVED as char, Color::Red)
+            .caption(format!("removed {removed} rolls"));
+        frame
     }
 
     pub fn solution_v2(&mut self) -> u64 {
         let mut count = 0u64;
 
-        for i in 0..N {
-            for j in 0..N {
-                if self.accessable(i, j) {
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                if self.accessable(x, y) {
                     count += 1;
-                    self.data[i][j] = REMOVED;
+                    self.grid[(x, y)] = REMOVED;
                 }
             }
         }
+        visualize::frame(|| self.frame(count));
         if count > 0 {
             count += self.solution_v2();
         }
@@ -81,39 +67,26 @@ impl<const N: usize> Grid<N> {
     }
 }
 
-impl<const N: usize> TryFrom<&str> for Grid<N> {
-    type Error = &'static str;
+impl TryFrom<&str> for Department {
+    type Error = GridError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut data = [[0u8; N]; N];
-
-        for (i, line) in value.lines().enumerate() {
-            let bytes = line.as_bytes();
-            for (j, &b) in bytes.iter().enumerate() {
-                data[i][j] = b;
-            }
-        }
-
-        Ok(Self::new(data))
+        Ok(Self {
+            grid: Grid::try_from(value)?,
+        })
     }
 }
 
-#[cfg(test)]
-const N: usize = 10;
-
-#[cfg(not(test))]
-const N: usize = 136;
-
 pub fn part_one(input: &str) -> Option<u64> {
-    let grid: Grid<N> = Grid::try_from(input).expect("Failed to parse grid");
+    let department = Department::try_from(input).expect("Failed to parse grid");
 
-    Some(grid.solution())
+    Some(department.solution())
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut grid: Grid<N> = Grid::try_from(input).expect("Failed to parse grid");
+    let mut department = Department::try_from(input).expect("Failed to parse grid");
 
-    Some(grid.solution_v2())
+    Some(department.solution_v2())
 }
 
 #[cfg(test)]
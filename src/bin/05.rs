@@ -1,6 +1,8 @@
 use std::ops::Range;
 
-advent_of_code::solution!(5);
+use advent_of_code::template::Rng;
+
+advent_of_code::solution!(5; generator = generate);
 
 #[derive(Debug)]
 pub struct DB {
@@ -45,7 +47,7 @@ impl DB {
     }
 
     pub fn solution_v2(&mut self) -> u64 {
-        self.ranges.sort_by(|r1, r2| r1.start.cmp(&r2.start));
+        self.ranges.sort_by_key(|r1| r1.start);
         let mut res = 0;
         let mut last = self.ranges[0].clone();
         for r in self.ranges.iter().skip(1) {
@@ -72,9 +74,33 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(db.solution_v2())
 }
 
+/// Overlapping and adjacent ranges of IDs below `size * 10`, followed by IDs to look up.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let max = size as u64 * 10;
+    let ranges = (0..rng.range(1..=size as u64))
+        .map(|_| {
+            let start = rng.below(max);
+            format!("{start}-{}\n", start + rng.below(size as u64 + 1))
+        })
+        .collect::<String>();
+    let ids = (0..rng.below(size as u64 + 1))
+        .map(|_| format!("{}\n", rng.below(max)))
+        .collect::<String>();
+    format!("{ranges}\n{ids}")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::property::Differential;
+    use std::collections::HashSet;
+
+    /// Counts the fresh IDs one by one.
+    fn part_two_set_count(input: &str) -> Option<u64> {
+        let db = DB::try_from(input).expect("invalid input");
+        let ids = db.ranges.into_iter().flatten().collect::<HashSet<_>>();
+        Some(ids.len() as u64)
+    }
 
     #[test]
     fn test_part_one() {
@@ -87,4 +113,9 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(14));
     }
+
+    #[test]
+    fn test_part_two_against_set_count() {
+        Differential::new(generate).check(part_two, part_two_set_count);
+    }
 }
@@ -1,3 +1,5 @@
+use advent_of_code::grid::Grid;
+
 advent_of_code::solution!(6);
 
 pub enum Op {
@@ -33,32 +35,6 @@ impl Op {
     }
 }
 
-pub fn transpose<T: Default + Clone + Copy>(matrix: &Vec<Vec<T>>) -> Vec<Vec<T>> {
-    if matrix.is_empty() {
-        return vec![];
-    }
-
-    let max_len = matrix.iter().map(|row| row.len()).max().unwrap_or(0);
-    if max_len == 0 {
-        return vec![];
-    }
-
-    let new_rows = max_len;
-    let new_cols = matrix.len();
-
-    let mut transposed = vec![vec![T::default(); new_cols]; new_rows];
-
-    for (i, row) in matrix.iter().enumerate() {
-        for (j, &item) in row.iter().enumerate() {
-            if j < new_rows {
-                transposed[j][i] = item;
-            }
-        }
-    }
-
-    transposed
-}
-
 pub fn part_one(input: &str) -> Option<u64> {
     let mut lines = input.lines().rev();
     let ops: Vec<Op> = lines
@@ -90,25 +66,14 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut lines = input.lines().rev();
-    let mut ops = lines
-        .next()
-        .expect("ops line not exists")
+    let (numbers, ops) = input.trim_end().rsplit_once('\n')?;
+    let mut ops = ops
         .split_whitespace()
         .map(|op| Op::try_from(op).expect("invalid op"));
 
-    let numbers = lines
-        .map(|line| line.as_bytes().iter().map(|b| *b).collect::<Vec<u8>>())
-        .collect::<Vec<Vec<u8>>>();
-    let transposed_numbers = transpose(&numbers);
-    let columns: Vec<String> = transposed_numbers
-        .iter()
-        .map(|line| {
-            let mut line = line.clone();
-            line.reverse();
-            String::from_utf8(line).expect("invalid input")
-        })
-        .collect();
+    // lines can be shorter than others if their trailing spaces were trimmed.
+    let numbers = Grid::parse_padded(numbers, ' ', |c| c).expect("invalid input");
+    let columns = numbers.columns().map(|column| column.collect::<String>());
 
     let mut res = 0;
     let mut curr_op = ops.next().expect("no op found");
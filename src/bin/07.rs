@@ -2,65 +2,78 @@ use std::collections::{HashMap, HashSet};
 
 advent_of_code::solution!(7);
 
+use advent_of_code::{
+    grid::Grid,
+    template::visualize::{self, Color, Frame},
+};
+
 pub fn part_one(input: &str) -> Option<u64> {
     let mut res = 0;
 
-    let mut lines = input.lines();
-    let start = lines.next()?.find(|b| b == 'S')?;
+    let grid = Grid::<char>::try_from(input).ok()?;
+    let (start, start_row) = grid.find(&'S')?;
     let mut beams: HashSet<_> = [start].into();
 
-    for line in lines {
+    for row in start_row + 1..grid.height() {
         let mut next_beams = HashSet::new();
-        for i in line
-            .chars()
-            .enumerate()
-            .filter(|(_, b)| *b == '^')
-            .map(|(i, _)| i)
-        {
+        for (i, _) in grid.row(row).iter().enumerate().filter(|(_, c)| **c == '^') {
             if !beams.remove(&i) {
                 continue;
             }
             if i > 0 {
                 next_beams.insert(i - 1);
             }
-            if i + 1 < line.len() {
+            if i + 1 < grid.width() {
                 next_beams.insert(i + 1);
             }
             res += 1;
         }
         beams.extend(next_beams);
+        visualize::frame(|| beam_frame(&grid, row, &beams, res));
     }
 
     Some(res)
 }
 
+/// Shows the manifold down to `row`, with the beams that leave it.
+fn beam_frame(grid: &Grid<char>, row: usize, beams: &HashSet<usize>, splits: u64) -> Frame {
+    let mut frame = Frame::from_fn(grid.width(), grid.height(), |x, y| grid[(x, y)]);
+    for &col in beams {
+        frame.set(col, row, '|').color(col, row, Color::Yellow);
+    }
+    frame
+        .color_all('^', Color::Cyan)
+        .caption(format!("{splits} splits"));
+    frame
+}
+
 pub fn part_two(input: &str) -> Option<u64> {
     let mut memo: HashMap<(usize, usize), u64> = HashMap::new();
 
-    let lines: Vec<&str> = input.lines().collect();
-    let start_col = lines[0].find(|b| b == 'S')?;
+    let grid = Grid::<char>::try_from(input).ok()?;
+    let (start_col, start_row) = grid.find(&'S')?;
 
     fn dfs(
         row: usize,
         col: usize,
         memo: &mut HashMap<(usize, usize), u64>,
-        lines: &Vec<&str>,
+        grid: &Grid<char>,
     ) -> u64 {
         if let Some(&res) = memo.get(&(row, col)) {
             return res;
         }
         let res = {
-            if row == lines.len() - 1 {
+            if row == grid.height() - 1 {
                 1
-            } else if lines[row].chars().nth(col).unwrap() != '^' {
-                dfs(row + 1, col, memo, lines)
+            } else if grid[(col, row)] != '^' {
+                dfs(row + 1, col, memo, grid)
             } else {
                 let mut res = 0;
                 if col > 0 {
-                    res += dfs(row + 1, col - 1, memo, lines);
+                    res += dfs(row + 1, col - 1, memo, grid);
                 }
-                if col + 1 < lines[row].len() {
-                    res += dfs(row + 1, col + 1, memo, lines);
+                if col + 1 < grid.width() {
+                    res += dfs(row + 1, col + 1, memo, grid);
                 }
                 res
             }
@@ -69,7 +82,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         res
     }
 
-    let res = dfs(1, start_col, &mut memo, &lines);
+    let res = dfs(start_row + 1, start_col, &mut memo, &grid);
     Some(res)
 }
 
@@ -1,6 +1,11 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 
-advent_of_code::solution!(8);
+use advent_of_code::{
+    disjoint_set::DisjointSet,
+    template::{Rng, phase},
+};
+
+advent_of_code::solution!(8; generator = generate);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Position3D {
@@ -40,14 +45,9 @@ impl TryFrom<&str> for Position3D {
     }
 }
 
+/// Indices of two positions.
 #[derive(Debug, Clone, Copy)]
-pub struct Pair(Position3D, Position3D);
-
-impl Pair {
-    pub fn distance(&self) -> f64 {
-        self.0.distance(&self.1)
-    }
-}
+pub struct Pair(usize, usize);
 
 #[cfg(not(test))]
 const CONNECTIONS: usize = 1000;
@@ -57,85 +57,55 @@ const CONNECTIONS: usize = 10;
 
 pub struct Solution {
     pub positions: Vec<Position3D>,
+    /// All pairs of positions, closest first.
     pub pairs: Vec<Pair>,
-    pub circuits: HashMap<usize, Vec<Position3D>>,
-    pub indexer: HashMap<Position3D, usize>,
+    pub circuits: DisjointSet,
 }
 
 impl TryFrom<&str> for Solution {
     type Error = &'static str;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let _t = phase("parse and sort pairs");
         let positions: Vec<Position3D> = s
             .lines()
-            .map(|line| Position3D::try_from(line))
+            .map(Position3D::try_from)
             .collect::<Result<_, _>>()?;
 
-        let mut pairs: Vec<Pair> = positions
-            .iter()
-            .enumerate()
-            .flat_map(|(i, pos1)| {
-                positions
-                    .iter()
-                    .skip(i + 1)
-                    .map(move |pos2| Pair(*pos1, *pos2))
-            })
+        let mut pairs: Vec<Pair> = (0..positions.len())
+            .flat_map(|i| (i + 1..positions.len()).map(move |j| Pair(i, j)))
             .collect();
 
-        pairs.sort_by(|a, b| a.distance().partial_cmp(&b.distance()).unwrap());
-
-        let mut circuits: HashMap<usize, Vec<Position3D>> = HashMap::new();
-        let mut indexer: HashMap<Position3D, usize> = HashMap::new();
-        positions.iter().enumerate().for_each(|(i, pos)| {
-            circuits.insert(i, vec![*pos]);
-            indexer.insert(*pos, i);
-        });
+        let distance = |pair: &Pair| positions[pair.0].distance(&positions[pair.1]);
+        pairs.sort_by(|a, b| distance(a).partial_cmp(&distance(b)).unwrap());
 
         Ok(Solution {
+            circuits: DisjointSet::new(positions.len()),
             positions,
             pairs,
-            circuits,
-            indexer,
         })
     }
 }
 
 impl Solution {
     pub fn part_one(&mut self) -> u64 {
+        let _t = phase("merge circuits");
         for pair in self.pairs.iter().take(CONNECTIONS) {
-            let c1 = self.indexer.get(&pair.0).unwrap().clone();
-            let c2 = self.indexer.get(&pair.1).unwrap().clone();
-            if c1 == c2 {
-                continue;
-            }
-            let postions = self.circuits.remove(&c2).unwrap();
-            self.circuits.get_mut(&c1).unwrap().extend(postions.iter());
-            for pos in postions {
-                self.indexer.insert(pos, c1);
-            }
+            self.circuits.union(pair.0, pair.1);
         }
 
-        let mut lens: Vec<u64> = self.circuits.values().map(|c| c.len() as u64).collect();
-        lens.sort();
-
-        lens.iter().rev().take(3).product()
+        self.circuits
+            .largest(3)
+            .iter()
+            .map(|size| *size as u64)
+            .product()
     }
 
     pub fn part_two(&mut self) -> u64 {
+        let _t = phase("merge circuits");
         for pair in self.pairs.iter() {
-            let c1 = self.indexer.get(&pair.0).unwrap().clone();
-            let c2 = self.indexer.get(&pair.1).unwrap().clone();
-            if c1 == c2 {
-                continue;
-            }
-            let postions = self.circuits.remove(&c2).unwrap();
-            self.circuits.get_mut(&c1).unwrap().extend(postions.iter());
-            for pos in postions {
-                self.indexer.insert(pos, c1);
-            }
-
-            if self.circuits.len() == 1 {
-                return pair.0.x * pair.1.x;
+            if self.circuits.union(pair.0, pair.1) && self.circuits.count() == 1 {
+                return self.positions[pair.0].x * self.positions[pair.1].x;
             }
         }
 
@@ -153,6 +123,19 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(sol.part_two())
 }
 
+/// `size` junction boxes at distinct positions. At least two, so that part two can connect them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut seen = HashSet::new();
+    let mut input = String::new();
+    while seen.len() < size.max(2) {
+        let position = (rng.below(100_000), rng.below(100_000), rng.below(100_000));
+        if seen.insert(position) {
+            input.push_str(&format!("{},{},{}\n", position.0, position.1, position.2));
+        }
+    }
+    input
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
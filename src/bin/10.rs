@@ -99,13 +99,7 @@ impl Machine {
             }
 
             // Find pivot
-            let mut pivot = None;
-            for row in pivot_row..n_equations {
-                if mat[row][col] == 1 {
-                    pivot = Some(row);
-                    break;
-                }
-            }
+            let pivot = (pivot_row..n_equations).find(|&row| mat[row][col] == 1);
 
             match pivot {
                 Some(row) => {
@@ -113,11 +107,14 @@ impl Machine {
                     mat.swap(pivot_row, row);
 
                     // Eliminate other rows
-                    for r in 0..n_equations {
-                        if r != pivot_row && mat[r][col] == 1 {
+                    let pivot_values = mat[pivot_row].clone();
+                    for (r, row) in mat.iter_mut().enumerate() {
+                        if r != pivot_row && row[col] == 1 {
                             // Row operation: r = r XOR pivot_row
-                            for k in col..=n_vars {
-                                mat[r][k] ^= mat[pivot_row][k];
+                            for (value, pivot_value) in
+                                row[col..].iter_mut().zip(&pivot_values[col..])
+                            {
+                                *value ^= pivot_value;
                             }
                         }
                     }
@@ -133,15 +130,13 @@ impl Machine {
 
         // Check for inconsistency
         // If a row is all zeros in A part but 1 in b, then 0 = 1 => impossible
-        for row in pivot_row..n_equations {
-            if mat[row][n_vars] == 1 {
-                // Inconsistent system
-                // Problem statement implies a solution exists ("What is the fewest...").
-                // But let's return a large number or 0 if we assume valid input.
-                // Given the phrasing, valid config is expected.
-                // For robustness, panic or return max.
-                panic!("No solution found for machine");
-            }
+        if mat[pivot_row..].iter().any(|row| row[n_vars] == 1) {
+            // Inconsistent system
+            // Problem statement implies a solution exists ("What is the fewest...").
+            // But let's return a large number or 0 if we assume valid input.
+            // Given the phrasing, valid config is expected.
+            // For robustness, panic or return max.
+            panic!("No solution found for machine");
         }
 
         // Try all combinations of free variables
@@ -224,13 +219,7 @@ impl Machine {
             }
 
             // Find pivot
-            let mut pivot = None;
-            for row in pivot_row..n_equations {
-                if mat[row][col].abs() > EPSILON {
-                    pivot = Some(row);
-                    break;
-                }
-            }
+            let pivot = (pivot_row..n_equations).find(|&row| mat[row][col].abs() > EPSILON);
 
             match pivot {
                 Some(row) => {
@@ -238,16 +227,19 @@ impl Machine {
 
                     // Normalize pivot row
                     let divisor = mat[pivot_row][col];
-                    for k in col..=n_vars {
-                        mat[pivot_row][k] /= divisor;
+                    for value in &mut mat[pivot_row][col..] {
+                        *value /= divisor;
                     }
 
                     // Eliminate other rows
-                    for r in 0..n_equations {
-                        if r != pivot_row && mat[r][col].abs() > EPSILON {
-                            let factor = mat[r][col];
-                            for k in col..=n_vars {
-                                mat[r][k] -= factor * mat[pivot_row][k];
+                    let pivot_values = mat[pivot_row].clone();
+                    for (r, row) in mat.iter_mut().enumerate() {
+                        if r != pivot_row && row[col].abs() > EPSILON {
+                            let factor = row[col];
+                            for (value, pivot_value) in
+                                row[col..].iter_mut().zip(&pivot_values[col..])
+                            {
+                                *value -= factor * pivot_value;
                             }
                         }
                     }
@@ -262,11 +254,12 @@ impl Machine {
         }
 
         // Check for inconsistency (0 = non-zero)
-        for row in pivot_row..n_equations {
-            if mat[row][n_vars].abs() > EPSILON {
-                // No solution
-                return 0; // Or handle error appropriately
-            }
+        if mat[pivot_row..]
+            .iter()
+            .any(|row| row[n_vars].abs() > EPSILON)
+        {
+            // No solution
+            return 0; // Or handle error appropriately
         }
 
         // Calculate upper bounds for free variables based on constraints
@@ -296,7 +289,6 @@ impl Machine {
             0,
             vec![0; n_vars],
             &mut min_total,
-            n_vars,
         );
 
         if min_total == u64::MAX {
@@ -315,7 +307,6 @@ impl Machine {
         idx: usize,
         mut solution: Vec<i64>,
         min_total: &mut u64,
-        n_vars: usize,
     ) {
         // Pruning based on current sum of free vars?
         // Pivot vars also add to sum, so partial sum is lower bound.
@@ -390,7 +381,6 @@ impl Machine {
                 idx + 1,
                 solution.clone(),
                 min_total,
-                n_vars,
             );
         }
     }
@@ -2,15 +2,22 @@ advent_of_code::solution!(12);
 
 use std::collections::HashSet;
 
+use advent_of_code::{grid::Grid, template::phase};
+
 pub fn part_one(input: &str) -> Option<u64> {
-    let (shapes, queries) = parse_input(input);
+    let (shapes, queries) = {
+        let _t = phase("parse");
+        parse_input(input)
+    };
     let mut count = 0;
 
     // Pre-calculate all unique variations for each shape.
-    let shape_variations: Vec<Vec<Shape>> =
-        shapes.iter().map(|s| s.generate_variations()).collect();
+    let shape_variations: Vec<Vec<Shape>> = {
+        let _t = phase("generate variations");
+        shapes.iter().map(|s| s.generate_variations()).collect()
+    };
 
-    for (_region_idx, (width, height, required_counts)) in queries.iter().enumerate() {
+    for (width, height, required_counts) in queries.iter() {
         // Optimization: Quick area check
         let mut total_area = 0;
         let mut items_to_place = Vec::new();
@@ -44,6 +51,7 @@ pub fn part_one(input: &str) -> Option<u64> {
             panic!("Grid width > 64 not supported by this optimization");
         }
 
+        let _t = phase("solve");
         if solve_recursive(
             &mut grid,
             *width,
@@ -74,20 +82,11 @@ struct Shape {
 }
 
 impl Shape {
-    fn new(lines: &[&str]) -> Self {
-        let height = lines.len();
-        let width = lines[0].len();
-        let mut points = Vec::new();
-        let mut area = 0;
-
-        for (r, line) in lines.iter().enumerate() {
-            for (c, ch) in line.chars().enumerate() {
-                if ch == '#' {
-                    points.push((r, c));
-                    area += 1;
-                }
-            }
-        }
+    fn new(grid: &Grid<char>) -> Self {
+        let height = grid.height();
+        let width = grid.width();
+        let points: Vec<(usize, usize)> = grid.find_all(&'#').map(|(c, r)| (r, c)).collect();
+        let area = points.len();
 
         let mut masks = vec![0u64; height];
         for &(r, c) in &points {
@@ -222,7 +221,7 @@ fn solve_recursive(
     // Symmetry breaking preparation
     let next_is_same = next_remaining
         .first()
-        .map_or(false, |&next_id| next_id == shape_id);
+        .is_some_and(|&next_id| next_id == shape_id);
 
     // Iteration logic to support start_r, start_c
     for r in start_r..height {
@@ -281,7 +280,9 @@ fn remove(grid: &mut [u64], shape: &Shape, r: usize, c: usize) {
     }
 }
 
-fn parse_input(input: &str) -> (Vec<Shape>, Vec<(usize, usize, Vec<usize>)>) {
+type Query = (usize, usize, Vec<usize>);
+
+fn parse_input(input: &str) -> (Vec<Shape>, Vec<Query>) {
     let mut shapes = Vec::new();
     let mut queries = Vec::new();
 
@@ -293,15 +294,15 @@ fn parse_input(input: &str) -> (Vec<Shape>, Vec<(usize, usize, Vec<usize>)>) {
 
         if let Some(first_line) = block.lines().next() {
             if first_line.contains(':')
-                && first_line.chars().nth(0).unwrap().is_digit(10)
+                && first_line.chars().next().unwrap().is_ascii_digit()
                 && !first_line.contains('x')
             {
                 // Shape
                 let id_str = first_line.strip_suffix(":").unwrap();
                 let _id: usize = id_str.parse().unwrap();
 
-                let grid_lines: Vec<&str> = block.lines().skip(1).collect();
-                let shape = Shape::new(&grid_lines);
+                let (_, shape_grid) = block.split_once('\n').unwrap();
+                let shape = Shape::new(&Grid::try_from(shape_grid).unwrap());
                 if shapes.len() <= _id {
                     shapes.resize(_id + 1, shape.clone());
                 }
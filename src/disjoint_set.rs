@@ -0,0 +1,130 @@
+/// A disjoint-set (union-find) structure, which tracks how elements `0..n` are joined into components,
+/// e.g. circuits of connected boxes or islands of a map.
+use std::cmp::Reverse;
+
+/// Components of the elements `0..len`, merged with path compression and union by size.
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    /// Size of the component of each root. Only up to date for roots.
+    sizes: Vec<usize>,
+    count: usize,
+}
+
+impl DisjointSet {
+    /// Creates `len` components with one element each.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            count: len,
+        }
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The number of components.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the root of the component of `x`, and points every element on the way directly at it.
+    ///
+    /// # Panics
+    /// Panics if `x` is not an element.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut x = x;
+        while self.parents[x] != root {
+            x = std::mem::replace(&mut self.parents[x], root);
+        }
+        root
+    }
+
+    /// Merges the components of `a` and `b`. Returns `false` if they already were the same component.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        // attach the smaller component, which keeps paths short.
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.count -= 1;
+        true
+    }
+
+    /// Whether `a` and `b` are in the same component.
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the component of `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.sizes[root]
+    }
+
+    /// The sizes of all components, in the order of their roots.
+    pub fn sizes(&self) -> Vec<usize> {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter(|(x, parent)| x == *parent)
+            .map(|(root, _)| self.sizes[root])
+            .collect()
+    }
+
+    /// The sizes of the `k` largest components, largest first.
+    pub fn largest(&self, k: usize) -> Vec<usize> {
+        let mut sizes = self.sizes();
+        sizes.sort_unstable_by_key(|size| Reverse(*size));
+        sizes.truncate(k);
+        sizes
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::DisjointSet;
+
+    #[test]
+    fn merges_components() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.count(), 6);
+
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 1));
+        assert!(set.union(3, 4));
+        assert!(!set.union(0, 2));
+
+        assert_eq!(set.count(), 3);
+        assert!(set.same(0, 2));
+        assert!(!set.same(0, 3));
+        assert_eq!(set.size(2), 3);
+        assert_eq!(set.size(5), 1);
+
+        let mut sizes = set.sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 2, 3]);
+        assert_eq!(set.largest(2), [3, 2]);
+        assert_eq!(set.largest(10), [3, 2, 1]);
+    }
+}
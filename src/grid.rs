@@ -0,0 +1,393 @@
+/// A rectangular grid of cells whose size is found at runtime, e.g. parsed from a puzzle input.
+/// Positions are `(x, y)` pairs, with `x` the column and `y` the row, like in [`Frame`](crate::template::visualize::Frame).
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+/// Offsets of the 4 orthogonal neighbours, clockwise from the top.
+const NEIGHBORS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of the 8 orthogonal and diagonal neighbours, clockwise from the top left.
+const NEIGHBORS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// An error which can be returned when parsing a grid.
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// The text has no lines.
+    Empty,
+    /// A line is longer or shorter than the first one. Lines are counted from 1.
+    Ragged {
+        line: usize,
+        width: usize,
+        expected: usize,
+    },
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "the grid has no lines."),
+            GridError::Ragged {
+                line,
+                width,
+                expected,
+            } => write!(
+                f,
+                "line {line} of the grid has {width} cells, but line 1 has {expected}."
+            ),
+        }
+    }
+}
+
+/// A grid of `width` by `height` cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid with each cell set to `cell(x, y)`.
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> T) -> Self {
+        Self {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| cell(x, y))
+                .collect(),
+        }
+    }
+
+    /// Parses a grid with one row per line, converting each character with `cell`.
+    /// All lines must have the same length.
+    pub fn parse(input: &str, cell: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        Self::from_lines(input, cell, None::<fn() -> T>)
+    }
+
+    /// Parses a grid like [`Grid::parse`], but fills up lines that are shorter than the longest one with `fill`,
+    /// e.g. lines whose trailing spaces were trimmed.
+    pub fn parse_padded(
+        input: &str,
+        fill: T,
+        cell: impl FnMut(char) -> T,
+    ) -> Result<Self, GridError>
+    where
+        T: Clone,
+    {
+        Self::from_lines(input, cell, Some(|| fill.clone()))
+    }
+
+    fn from_lines(
+        input: &str,
+        mut cell: impl FnMut(char) -> T,
+        fill: Option<impl Fn() -> T>,
+    ) -> Result<Self, GridError> {
+        let lines: Vec<&str> = input.lines().collect();
+        let widths: Vec<usize> = lines.iter().map(|line| line.chars().count()).collect();
+        let width = match &fill {
+            Some(_) => widths.iter().copied().max(),
+            None => widths.first().copied(),
+        }
+        .ok_or(GridError::Empty)?;
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            cells.extend(line.chars().map(&mut cell));
+            match &fill {
+                Some(fill) => cells.resize_with(width * (i + 1), fill),
+                None if widths[i] != width => {
+                    return Err(GridError::Ragged {
+                        line: i + 1,
+                        width: widths[i],
+                        expected: width,
+                    });
+                }
+                None => {}
+            }
+        }
+
+        Ok(Self {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    /// Returns the cell at `(x, y)`, or `None` if it is outside of the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    /// Returns the cell at `(x, y)` mutably, or `None` if it is outside of the grid.
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+    /// Returns the cell at `(x, y)`, or `None` if it is outside of the grid. Accepts negative positions, e.g. `x - 1`.
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<&T> {
+        self.get(usize::try_from(x).ok()?, usize::try_from(y).ok()?)
+    }
+
+    /// Returns the cell at `(x, y)` on a grid that repeats infinitely in every direction.
+    ///
+    /// # Panics
+    /// Panics if the grid is empty.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        let (x, y) = (
+            x.rem_euclid(self.width as isize) as usize,
+            y.rem_euclid(self.height as isize) as usize,
+        );
+        &self[(x, y)]
+    }
+
+    /// Returns the positions of the cells above, right of, below and left of `(x, y)` that are inside of the grid.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(x, y, &NEIGHBORS_4)
+    }
+
+    /// Returns the positions of the orthogonal and diagonal neighbours of `(x, y)` that are inside of the grid.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(x, y, &NEIGHBORS_8)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let x = x.checked_add_signed(*dx)?;
+            let y = y.checked_add_signed(*dy)?;
+            self.index_of(x, y).map(|_| (x, y))
+        })
+    }
+
+    /// Returns the cells of row `y`.
+    ///
+    /// # Panics
+    /// Panics if the row is outside of the grid.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is outside of the grid");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Returns the rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Returns the cells of column `x`, from top to bottom.
+    ///
+    /// # Panics
+    /// Panics if the column is outside of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside of the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Returns the columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Returns a grid whose rows are the columns of this one.
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+
+    /// Returns the position and value of every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| ((i % self.width, i / self.width), cell))
+    }
+
+    /// Returns the position of the first cell with the given value, row by row, e.g. of the start `S` of a maze.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find(|(_, cell)| *cell == value)
+            .map(|(position, _)| position)
+    }
+
+    /// Returns the positions of all cells with the given value, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(position, _)| position)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// Returns the cell at `(x, y)`. Panics if it is outside of the grid.
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside of the grid"))
+    }
+}
+
+impl TryFrom<&str> for Grid<char> {
+    type Error = GridError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value, |c| c)
+    }
+}
+
+impl TryFrom<&str> for Grid<u8> {
+    type Error = GridError;
+
+    /// Parses ASCII grids. Other characters are truncated to their lowest byte.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        #[allow(clippy::cast_possible_truncation)]
+        Self::parse(value, |c| c as u8)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    /// Writes one line per row, without a trailing newline, so that the grid can be returned as an answer.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Grid, GridError};
+
+    fn grid() -> Grid<char> {
+        Grid::try_from("ab.\n.S#\n").unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 1)], 'S');
+        assert_eq!(grid.find(&'S'), Some((1, 1)));
+        assert_eq!(grid.find_all(&'.').collect::<Vec<_>>(), [(2, 0), (0, 1)]);
+        assert_eq!(grid.to_string(), "ab.\n.S#");
+
+        assert_eq!(
+            Grid::<char>::try_from("ab\nabc"),
+            Err(GridError::Ragged {
+                line: 2,
+                width: 3,
+                expected: 2
+            })
+        );
+        assert_eq!(Grid::<char>::try_from(""), Err(GridError::Empty));
+
+        let padded = Grid::parse_padded("ab\nabc", ' ', |c| c).unwrap();
+        assert_eq!(padded.to_string(), "ab \nabc");
+    }
+
+    #[test]
+    fn accesses_cells() {
+        let mut grid = grid();
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(0, 0), Some(&'a'));
+        assert_eq!(grid.get_wrapping(-1, 2), &'.');
+        assert_eq!(grid.get_wrapping(4, -1), &'S');
+
+        grid[(0, 0)] = 'x';
+        assert_eq!(grid.row(0), ['x', 'b', '.']);
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbors4(0, 1).collect::<Vec<_>>(),
+            [(0, 0), (1, 1), (0, 2)]
+        );
+        assert_eq!(grid.neighbors8(1, 1).count(), 8);
+        assert_eq!(
+            grid.neighbors8(2, 2).collect::<Vec<_>>(),
+            [(1, 1), (2, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn views_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["a.", "bS", ".#"]
+        );
+        assert_eq!(grid.transposed().to_string(), "a.\nbS\n.#");
+        assert_eq!(grid.rows().map(|row| row.len()).collect::<Vec<_>>(), [3, 3]);
+    }
+}
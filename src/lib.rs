@@ -1,3 +1,5 @@
+pub mod disjoint_set;
+pub mod grid;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
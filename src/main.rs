@@ -1,32 +1,48 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
-use args::{AppArguments, parse};
-
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
 #[cfg(feature = "today")]
+use advent_of_code::template::commands::today;
+use advent_of_code::template::commands::{
+    all, completions, download, read, scaffold, solve, time, timings, verify,
+};
+#[cfg(feature = "encrypted-inputs")]
+use advent_of_code::template::commands::{check_inputs, decrypt, encrypt};
+use advent_of_code::template::config;
+use args::{AppArguments, parse};
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        Day,
+        cli::{self, ArgsError},
+        visualize,
+    };
+    use std::{env, path::PathBuf};
 
     pub enum AppArguments {
+        Help {
+            usage: String,
+        },
+        Version,
         Download {
             day: Day,
         },
         Read {
             day: Day,
+            part: Option<u8>,
         },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            overwrite_data: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            part: Option<u8>,
+            visualize: Option<visualize::Settings>,
         },
         All {
             release: bool,
@@ -35,110 +51,284 @@ mod args {
             all: bool,
             day: Option<Day>,
             store: bool,
+            part: Option<u8>,
+            scale: bool,
+        },
+        CompareTimings {
+            files: Vec<PathBuf>,
+        },
+        Verify {
+            day: Option<Day>,
+            all_inputs: bool,
+            release: bool,
+        },
+        Completions {
+            shell: String,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
+        #[cfg(feature = "encrypted-inputs")]
+        Encrypt,
+        #[cfg(feature = "encrypted-inputs")]
+        Decrypt,
+        #[cfg(feature = "encrypted-inputs")]
+        CheckInputs,
+    }
+
+    pub fn parse() -> Result<AppArguments, ArgsError> {
+        let raw: Vec<String> = env::args_os()
+            .skip(1)
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        let is_set = |flags: [&str; 2]| raw.iter().any(|arg| flags.contains(&arg.as_str()));
+
+        let Some(name) = raw.first().filter(|arg| !arg.starts_with('-')) else {
+            cli::check_flags(None, &raw)?;
+            return if is_set(["-V", "--version"]) {
+                Ok(AppArguments::Version)
+            } else if is_set(["-h", "--help"]) {
+                Ok(AppArguments::Help {
+                    usage: cli::usage(),
+                })
+            } else {
+                Err(ArgsError::MissingCommand)
+            };
+        };
+
+        let command = cli::find_command(name).ok_or_else(|| cli::unknown_command(name))?;
+        cli::check_flags(Some(command), &raw[1..])?;
+
+        let mut args = pico_args::Arguments::from_vec(raw[1..].iter().map(Into::into).collect());
+
+        if args.contains(["-h", "--help"]) {
+            return Ok(AppArguments::Help {
+                usage: command.usage(),
+            });
+        }
+
+        #[cfg(not(feature = "today"))]
+        if command.name == "today" {
+            return Err(ArgsError::InvalidArgument {
+                command: command.name,
+                message: "the `today` feature is not enabled, run it via `cargo today`".into(),
+            });
+        }
+
+        #[cfg(not(feature = "encrypted-inputs"))]
+        if matches!(command.name, "encrypt" | "decrypt" | "check-inputs") {
+            return Err(ArgsError::InvalidArgument {
+                command: command.name,
+                message: format!(
+                    "the `encrypted-inputs` feature is not enabled, run it via `cargo {}`",
+                    command.name
+                ),
+            });
+        }
+
+        let app_args =
+            parse_command(command.name, &mut args).map_err(|e| ArgsError::InvalidArgument {
+                command: command.name,
+                message: match e {
+                    pico_args::Error::MissingArgument => "missing a required argument".into(),
+                    e => e.to_string(),
+                },
+            })?;
+
+        if let Some(argument) = args.finish().first() {
+            return Err(ArgsError::UnexpectedArgument {
+                command: command.name,
+                argument: argument.to_string_lossy().into_owned(),
+            });
+        }
+
+        Ok(app_args)
+    }
+
+    fn parse_part(s: &str) -> Result<u8, &'static str> {
+        match s.parse() {
+            Ok(part @ (1 | 2)) => Ok(part),
+            _ => Err("expecting part 1 or 2"),
+        }
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    fn parse_timings_action(s: &str) -> Result<(), &'static str> {
+        match s {
+            "compare" => Ok(()),
+            _ => Err("expecting `compare`"),
+        }
+    }
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+    fn parse_command(
+        name: &str,
+        args: &mut pico_args::Arguments,
+    ) -> Result<AppArguments, pico_args::Error> {
+        let app_args = match name {
+            "all" => AppArguments::All {
                 release: args.contains("--release"),
             },
-            Some("time") => {
+            "time" => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let part = args.opt_value_from_fn("--part", parse_part)?;
+                let scale = args.contains("--scale");
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    part,
+                    scale,
                 }
             }
-            Some("download") => AppArguments::Download {
+            "download" => AppArguments::Download {
                 day: args.free_from_str()?,
             },
-            Some("read") => AppArguments::Read {
+            "read" => AppArguments::Read {
+                part: args.opt_value_from_fn("--part", parse_part)?,
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+            "scaffold" => AppArguments::Scaffold {
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
+                overwrite_data: args.contains("--overwrite-data"),
+                template: args.opt_value_from_str("--template")?,
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
             },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+            "solve" => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_fn("--submit", parse_part)?;
+                let dhat = args.contains("--dhat");
+                let part = args.opt_value_from_fn("--part", parse_part)?;
+
+                // like in the solution binaries, `--fps` and `--frames` imply `--visualize`.
+                let show = args.contains("--visualize");
+                let fps = args.opt_value_from_str("--fps")?;
+                let frames = args.opt_value_from_str("--frames")?;
+                let visualize =
+                    (show || fps.is_some() || frames.is_some()).then(|| visualize::Settings {
+                        fps: fps.unwrap_or(visualize::DEFAULT_FPS),
+                        frames,
+                    });
+
+                AppArguments::Solve {
+                    release,
+                    submit,
+                    dhat,
+                    part,
+                    visualize,
+                    day: args.free_from_str()?,
+                }
             }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            "timings" => {
+                args.free_from_fn(parse_timings_action)?;
+
+                let mut files = vec![];
+                while let Some(file) = args.opt_free_from_str()? {
+                    files.push(file);
+                }
+
+                AppArguments::CompareTimings { files }
             }
+            "verify" => AppArguments::Verify {
+                all_inputs: args.contains("--all-inputs"),
+                release: args.contains("--release"),
+                day: args.opt_free_from_str()?,
+            },
+            "completions" => AppArguments::Completions {
+                shell: args.free_from_str()?,
+            },
+            #[cfg(feature = "today")]
+            "today" => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
+            #[cfg(feature = "encrypted-inputs")]
+            "encrypt" => AppArguments::Encrypt,
+            #[cfg(feature = "encrypted-inputs")]
+            "decrypt" => AppArguments::Decrypt,
+            #[cfg(feature = "encrypted-inputs")]
+            "check-inputs" => AppArguments::CheckInputs,
+            _ => unreachable!("command \"{name}\" is listed in `cli::COMMANDS`, but not parsed"),
         };
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
-
         Ok(app_args)
     }
 }
 
 fn main() {
-    match parse() {
+    let args = match parse() {
+        Ok(args) => args,
         Err(err) => {
             eprintln!("Error: {err}");
-            std::process::exit(1);
+            process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+    };
+
+    if let Err(err) = config::init() {
+        eprintln!("Error: {err}");
+        process::exit(1);
+    }
+
+    let result = match args {
+        AppArguments::Help { usage } => {
+            print!("{usage}");
+            Ok(())
+        }
+        AppArguments::Version => {
+            println!("advent_of_code {}", env!("CARGO_PKG_VERSION"));
+            Ok(())
+        }
+        AppArguments::All { release } => all::handle(release),
+        AppArguments::Time {
+            day,
+            all,
+            store,
+            part,
+            scale,
+        } => time::handle(day, all, store, part, scale).map(drop),
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::Read { day, part } => read::handle(day, part),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+            overwrite_data,
+            template,
+        } => scaffold::handle(day, overwrite, overwrite_data, template.as_deref()).and_then(|_| {
+            if download {
+                download::handle(day)
+            } else {
+                Ok(())
             }
-        },
+        }),
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            part,
+            visualize,
+        } => solve::handle(day, release, dhat, submit, part, visualize),
+        AppArguments::CompareTimings { files } => timings::compare(&files),
+        AppArguments::Verify {
+            day,
+            all_inputs,
+            release,
+        } => verify::handle(day, all_inputs, release),
+        AppArguments::Completions { shell } => completions::handle(&shell),
+        #[cfg(feature = "today")]
+        AppArguments::Today { wait } => today::handle(wait).map(drop),
+        #[cfg(feature = "encrypted-inputs")]
+        AppArguments::Encrypt => encrypt::handle().map(drop),
+        #[cfg(feature = "encrypted-inputs")]
+        AppArguments::Decrypt => decrypt::handle().map(drop),
+        #[cfg(feature = "encrypted-inputs")]
+        AppArguments::CheckInputs => check_inputs::handle(),
     };
+
+    if let Err(err) = result {
+        eprintln!("Error: {err}");
+        process::exit(1);
+    }
 }
@@ -0,0 +1,213 @@
+/// The answer of a part, which the runner prints and submits.
+use std::fmt::Display;
+
+use crate::{grid::Grid, template::ocr};
+
+/// An answer of a part. Part functions may return any type that converts into it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u128),
+    Signed(i128),
+    /// A single line of text.
+    Text(String),
+    /// Multiple lines, one string per row. Usually letters drawn with pixels, see [`ocr`].
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Returns the answer as a single line, as it would be entered on the website.
+    /// Grids are decoded to the letters they draw, which fails for grids that are not made of letters.
+    pub fn text(&self) -> Option<String> {
+        match self {
+            Answer::Unsigned(x) => Some(x.to_string()),
+            Answer::Signed(x) => Some(x.to_string()),
+            Answer::Text(s) => Some(s.clone()),
+            Answer::Grid(rows) => ocr::decode(&rows.join("\n")),
+        }
+    }
+
+    /// Returns the answer as a number, if it is one that fits into an `i128`.
+    pub fn number(&self) -> Option<i128> {
+        match self {
+            Answer::Unsigned(x) => i128::try_from(*x).ok(),
+            Answer::Signed(x) => Some(*x),
+            Answer::Text(_) | Answer::Grid(_) => None,
+        }
+    }
+
+    /// Name of the variant, used in structured output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Unsigned(_) => "unsigned",
+            Answer::Signed(_) => "signed",
+            Answer::Text(_) => "text",
+            Answer::Grid(_) => "grid",
+        }
+    }
+
+    /// Returns the text to submit, or the reason why the answer cannot be correct.
+    /// Puzzle answers are never zero or negative, and never empty.
+    pub fn submission(&self) -> Result<String, String> {
+        match self {
+            Answer::Unsigned(0) | Answer::Signed(0) => Err("the answer is 0".into()),
+            Answer::Signed(x) if *x < 0 => Err(format!("the answer {x} is negative")),
+            Answer::Text(s) if s.trim().is_empty() => Err("the answer is empty".into()),
+            Answer::Grid(_) => self
+                .text()
+                .ok_or_else(|| "the answer is a grid that does not spell any letters".into()),
+            _ => self.text().ok_or_else(|| "the answer is empty".into()),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(x) => write!(f, "{x}"),
+            Answer::Signed(x) => write!(f, "{x}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(Unsigned, u128, u8, u16, u32, u64, usize);
+impl_from_integer!(Signed, i128, i8, i16, i32, i64, isize);
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        Answer::Unsigned(value)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        Answer::Signed(value)
+    }
+}
+
+impl From<String> for Answer {
+    /// Text with line breaks becomes a grid.
+    fn from(value: String) -> Self {
+        if value.contains('\n') {
+            Answer::Grid(value.lines().map(str::to_string).collect())
+        } else {
+            Answer::Text(value)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::from(value.to_string())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(value: char) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(value: Vec<String>) -> Self {
+        Answer::Grid(value)
+    }
+}
+
+impl From<Vec<Vec<char>>> for Answer {
+    fn from(value: Vec<Vec<char>>) -> Self {
+        Answer::Grid(value.iter().map(|row| row.iter().collect()).collect())
+    }
+}
+
+impl From<Vec<Vec<bool>>> for Answer {
+    /// Lit pixels are drawn as `#`, unlit ones as `.`.
+    fn from(value: Vec<Vec<bool>>) -> Self {
+        Answer::Grid(
+            value
+                .iter()
+                .map(|row| row.iter().map(|&x| if x { '#' } else { '.' }).collect())
+                .collect(),
+        )
+    }
+}
+
+impl<T: Display> From<Grid<T>> for Answer {
+    fn from(value: Grid<T>) -> Self {
+        Answer::Grid(
+            value
+                .rows()
+                .map(|row| row.iter().map(ToString::to_string).collect())
+                .collect(),
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::Answer;
+    use crate::grid::Grid;
+
+    #[test]
+    fn converts_common_types() {
+        assert_eq!(Answer::from(42_u64), Answer::Unsigned(42));
+        assert_eq!(
+            Answer::from(usize::MAX),
+            Answer::Unsigned(usize::MAX as u128)
+        );
+        assert_eq!(Answer::from(-3_i32), Answer::Signed(-3));
+        assert_eq!(Answer::from("abc"), Answer::Text("abc".into()));
+        assert_eq!(Answer::from('x'), Answer::Text("x".into()));
+        assert_eq!(
+            Answer::from("#.\n.#".to_string()),
+            Answer::Grid(vec!["#.".into(), ".#".into()])
+        );
+        assert_eq!(
+            Answer::from(vec![vec![true, false], vec![false, true]]),
+            Answer::Grid(vec!["#.".into(), ".#".into()])
+        );
+        assert_eq!(
+            Answer::from(Grid::<char>::try_from("#.\n.#").unwrap()),
+            Answer::Grid(vec!["#.".into(), ".#".into()])
+        );
+    }
+
+    #[test]
+    fn formats_answers() {
+        assert_eq!(Answer::Signed(-3).to_string(), "-3");
+        assert_eq!(
+            Answer::Grid(vec!["#.".into(), ".#".into()]).to_string(),
+            "#.\n.#"
+        );
+        assert_eq!(Answer::Unsigned(u128::MAX).number(), None);
+        assert_eq!(Answer::Signed(-3).number(), Some(-3));
+    }
+
+    #[test]
+    fn validates_submissions() {
+        assert_eq!(Answer::Unsigned(7).submission(), Ok("7".into()));
+        assert!(Answer::Unsigned(0).submission().is_err());
+        assert!(Answer::Signed(-1).submission().is_err());
+        assert!(Answer::Text(" ".into()).submission().is_err());
+        assert!(Answer::Grid(vec!["#.#".into()]).submission().is_err());
+
+        let letters = ["####", "#...", "###.", "#...", "#...", "####"];
+        let grid = Answer::Grid(letters.iter().map(|x| x.to_string()).collect());
+        assert_eq!(grid.submission(), Ok("E".into()));
+    }
+}
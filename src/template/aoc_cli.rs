@@ -4,94 +4,173 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{
+    Answer, Day, config,
+    submissions::{Submissions, Verdict},
+};
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    /// The answer is known to be wrong, so it was not submitted.
+    RejectedAnswer(String),
 }
 
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
+            AocCommandError::CommandNotFound => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::RejectedAnswer(reason) => {
+                write!(f, "refusing to submit: {reason}.")
+            }
         }
     }
 }
 
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
+    Command::new(&config::get().aoc_cli)
         .arg("-V")
         .output()
         .map_err(|_| AocCommandError::CommandNotFound)?;
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
+pub fn download(day: Day) -> Result<Output, AocCommandError> {
+    download_with(day, Stdio::inherit())
+}
+
+/// Like [`download`], but captures the error output of aoc-cli so that callers can tell why it failed,
+/// see [`is_puzzle_locked`]. The error output is printed once aoc-cli has exited.
+pub fn download_capturing(day: Day) -> Result<Output, AocCommandError> {
+    download_with(day, Stdio::piped())
+}
+
+fn download_with(day: Day, stderr: Stdio) -> Result<Output, AocCommandError> {
+    let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
 
     let args = build_args(
-        "read",
+        "download",
         &[
-            "--description-only".into(),
+            "--overwrite".into(),
+            "--input-file".into(),
+            input_path.to_string(),
             "--puzzle-file".into(),
-            puzzle_path,
+            puzzle_path.to_string(),
         ],
         day,
     );
 
-    call_aoc_cli(&args)
+    let output = call_aoc_cli(&args, stderr)?;
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(output)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
+/// Downloads only the puzzle description, leaving the input untouched.
+pub fn download_puzzle(day: Day) -> Result<Output, AocCommandError> {
     let puzzle_path = get_puzzle_path(day);
 
     let args = build_args(
         "download",
         &[
             "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
+            "--puzzle-only".into(),
             "--puzzle-file".into(),
             puzzle_path.to_string(),
         ],
         day,
     );
 
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+    call_aoc_cli(&args, Stdio::inherit())
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submits an answer, unless it cannot be correct or is known to be wrong from earlier submissions.
+/// Wrong answers are recorded, see [`Submissions`].
+pub fn submit(day: Day, part: u8, answer: &Answer) -> Result<Output, AocCommandError> {
+    let result = answer
+        .submission()
+        .map_err(AocCommandError::RejectedAnswer)?;
+    Submissions::read(day)
+        .check(part, answer, &result)
+        .map_err(AocCommandError::RejectedAnswer)?;
+
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
+    args.push(result.clone());
+
+    // capture stdout so callers can inspect and print the response.
+    let output = Command::new(&config::get().aoc_cli)
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    if let Some(verdict) = Verdict::parse(&String::from_utf8_lossy(&output.stdout))
+        && let Err(e) = Submissions::record(day, part, verdict, &result)
+    {
+        eprintln!("failed to record wrong answer: {e}");
+    }
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
+
+/// Whether the response to a submission shows that the part is solved,
+/// either by this submission or an earlier one.
+pub fn is_part_solved(output: &Output) -> bool {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout.contains("That's the right answer") || stdout.contains("Did you already complete it?")
+}
+
+/// Whether a download failed because the puzzle is not unlocked yet, or not served yet right after it unlocked.
+/// Only works for the errors of [`download_capturing`].
+pub fn is_puzzle_locked(error: &AocCommandError) -> bool {
+    let AocCommandError::BadExitStatus(output) = error else {
+        return false;
+    };
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    stderr.contains("still locked") || stderr.contains("404 Not Found")
 }
 
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    config::get()
+        .paths
+        .inputs
+        .join(format!("{day}.txt"))
+        .display()
+        .to_string()
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    config::get()
+        .paths
+        .puzzles
+        .join(format!("{day}.md"))
+        .display()
+        .to_string()
 }
 
-fn get_year() -> Option<u16> {
+/// The event year, read from the `AOC_YEAR` environment variable or `aoc.toml`.
+pub(crate) fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+        Err(_) => config::get().year,
     }
 }
 
@@ -108,14 +187,16 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+fn call_aoc_cli(args: &[String], stderr: Stdio) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    let output = Command::new(&config::get().aoc_cli)
         .args(args)
         .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
+        .stderr(stderr)
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
+    // empty unless stderr was captured.
+    eprint!("{}", String::from_utf8_lossy(&output.stderr));
 
     if output.status.success() {
         Ok(output)
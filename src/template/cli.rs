@@ -0,0 +1,658 @@
+//! Declarative description of the `advent_of_code` command-line interface.
+//! This is used to generate help texts, argument errors and shell completions,
+//! and has to be kept in sync with the parser in `main.rs`.
+
+use std::{error::Error, fmt::Display};
+
+/// How the positional day argument of a command is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayArg {
+    /// The command does not take a day.
+    None,
+    /// The command requires a day that has been scaffolded.
+    Required,
+    /// The command optionally takes a day that has been scaffolded.
+    Optional,
+    /// The command requires a day that does not need to exist yet.
+    New,
+}
+
+/// The kind of value a flag expects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlagValue {
+    Part,
+    Template,
+    Shell,
+    Path,
+    Fps,
+    /// The `compare` action of `timings`, followed by the files to compare.
+    TimingsAction,
+}
+
+impl FlagValue {
+    fn placeholder(self) -> &'static str {
+        match self {
+            FlagValue::Part => "<part>",
+            FlagValue::Template => "<template>",
+            FlagValue::Shell => "<shell>",
+            FlagValue::Path => "<path>",
+            FlagValue::Fps => "<fps>",
+            FlagValue::TimingsAction => "compare <file> <file>...",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Flag {
+    pub name: &'static str,
+    pub value: Option<FlagValue>,
+    pub about: &'static str,
+}
+
+#[derive(Debug)]
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub day: DayArg,
+    pub positional: Option<FlagValue>,
+    pub flags: &'static [Flag],
+}
+
+/// Supported shells for `completions`.
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "scaffold",
+        about: "Create the solution, input and example files for a day",
+        day: DayArg::New,
+        positional: None,
+        flags: &[
+            Flag {
+                name: "--download",
+                value: None,
+                about: "Download input and puzzle description afterwards",
+            },
+            Flag {
+                name: "--overwrite",
+                value: None,
+                about: "Overwrite an existing solution file",
+            },
+            Flag {
+                name: "--overwrite-data",
+                value: None,
+                about: "Back up and replace non-empty input and example files",
+            },
+            Flag {
+                name: "--template",
+                value: Some(FlagValue::Template),
+                about: "Module template to scaffold from",
+            },
+        ],
+    },
+    Command {
+        name: "download",
+        about: "Download input and puzzle description for a day",
+        day: DayArg::Required,
+        positional: None,
+        flags: &[],
+    },
+    Command {
+        name: "read",
+        about: "Show the puzzle description for a day",
+        day: DayArg::Required,
+        positional: None,
+        flags: &[Flag {
+            name: "--part",
+            value: Some(FlagValue::Part),
+            about: "Only show one part of the description",
+        }],
+    },
+    Command {
+        name: "solve",
+        about: "Run the solution for a day",
+        day: DayArg::Required,
+        positional: None,
+        flags: &[
+            Flag {
+                name: "--release",
+                value: None,
+                about: "Run an optimized build",
+            },
+            Flag {
+                name: "--dhat",
+                value: None,
+                about: "Profile heap allocations with DHAT",
+            },
+            Flag {
+                name: "--submit",
+                value: Some(FlagValue::Part),
+                about: "Submit the answer for a part via aoc-cli",
+            },
+            Flag {
+                name: "--part",
+                value: Some(FlagValue::Part),
+                about: "Only run one part",
+            },
+            Flag {
+                name: "--visualize",
+                value: None,
+                about: "Show the frames of visualisations",
+            },
+            Flag {
+                name: "--fps",
+                value: Some(FlagValue::Fps),
+                about: "Show visualisations at this many frames per second (default: 10)",
+            },
+            Flag {
+                name: "--frames",
+                value: Some(FlagValue::Path),
+                about: "Write the frames of visualisations to a file instead of showing them",
+            },
+        ],
+    },
+    Command {
+        name: "all",
+        about: "Run the solutions for all days",
+        day: DayArg::None,
+        positional: None,
+        flags: &[Flag {
+            name: "--release",
+            value: None,
+            about: "Run optimized builds",
+        }],
+    },
+    Command {
+        name: "time",
+        about: "Benchmark solutions",
+        day: DayArg::Optional,
+        positional: None,
+        flags: &[
+            Flag {
+                name: "--all",
+                value: None,
+                about: "Benchmark all days, including ones that are already stored",
+            },
+            Flag {
+                name: "--store",
+                value: None,
+                about: "Store the timings in the readme",
+            },
+            Flag {
+                name: "--part",
+                value: Some(FlagValue::Part),
+                about: "Only benchmark one part",
+            },
+            Flag {
+                name: "--scale",
+                value: None,
+                about: "Time a day on generated inputs of growing size and estimate its complexity",
+            },
+        ],
+    },
+    Command {
+        name: "timings",
+        about: "Compare timing files, e.g. from different machines",
+        day: DayArg::None,
+        positional: Some(FlagValue::TimingsAction),
+        flags: &[],
+    },
+    Command {
+        name: "verify",
+        about: "Check solutions against the known answers of their inputs",
+        day: DayArg::Optional,
+        positional: None,
+        flags: &[
+            Flag {
+                name: "--all-inputs",
+                value: None,
+                about: "Also check the named inputs in `data/inputs/NN/`",
+            },
+            Flag {
+                name: "--release",
+                value: None,
+                about: "Run optimized builds",
+            },
+        ],
+    },
+    // `cargo today` always enables the `today` feature, so the command is listed unconditionally.
+    Command {
+        name: "today",
+        about: "Scaffold, download and read the current day",
+        day: DayArg::None,
+        positional: None,
+        flags: &[Flag {
+            name: "--wait",
+            value: None,
+            about: "Wait for the next puzzle to unlock",
+        }],
+    },
+    // like `today`, these commands are run via aliases that enable the `encrypted-inputs` feature.
+    Command {
+        name: "encrypt",
+        about: "Encrypt all inputs, creating a key if there is none",
+        day: DayArg::None,
+        positional: None,
+        flags: &[],
+    },
+    Command {
+        name: "decrypt",
+        about: "Decrypt all encrypted inputs",
+        day: DayArg::None,
+        positional: None,
+        flags: &[],
+    },
+    Command {
+        name: "check-inputs",
+        about: "Check that git does not track any plaintext inputs",
+        day: DayArg::None,
+        positional: None,
+        flags: &[],
+    },
+    Command {
+        name: "completions",
+        about: "Print a shell completion script",
+        day: DayArg::None,
+        positional: Some(FlagValue::Shell),
+        flags: &[],
+    },
+];
+
+/// Arguments of the solution binaries generated by `solution!`. These are not a command of `advent_of_code`.
+pub const SOLUTION: Command = Command {
+    name: "solution",
+    about: "Run the solution for a day",
+    day: DayArg::None,
+    positional: None,
+    flags: &[
+        Flag {
+            name: "--part",
+            value: Some(FlagValue::Part),
+            about: "Only run one part",
+        },
+        Flag {
+            name: "--time",
+            value: None,
+            about: "Benchmark the parts",
+        },
+        Flag {
+            name: "--submit",
+            value: Some(FlagValue::Part),
+            about: "Submit the answer for a part via aoc-cli",
+        },
+        Flag {
+            name: "--input",
+            value: Some(FlagValue::Path),
+            about: "Read the input from a file instead of the inputs folder",
+        },
+        Flag {
+            name: "--json",
+            value: None,
+            about: "Print the results as JSON",
+        },
+        Flag {
+            name: "--quiet",
+            value: None,
+            about: "Only print the answers",
+        },
+        Flag {
+            name: "--visualize",
+            value: None,
+            about: "Show the frames of visualisations",
+        },
+        Flag {
+            name: "--fps",
+            value: Some(FlagValue::Fps),
+            about: "Show visualisations at this many frames per second (default: 10)",
+        },
+        Flag {
+            name: "--frames",
+            value: Some(FlagValue::Path),
+            about: "Write the frames of visualisations to a file instead of showing them",
+        },
+        Flag {
+            name: "--scale",
+            value: None,
+            about: "Time the parts on generated inputs of growing size and estimate their complexity",
+        },
+    ],
+};
+
+const HELP_FLAG: Flag = Flag {
+    name: "--help",
+    value: None,
+    about: "Print help",
+};
+
+/// Flags that are accepted without a command.
+pub const GLOBAL_FLAGS: &[Flag] = &[
+    HELP_FLAG,
+    Flag {
+        name: "--version",
+        value: None,
+        about: "Print version",
+    },
+];
+
+/// Returns the command with the given name.
+pub fn find_command(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|command| command.name == name)
+}
+
+fn write_flags(out: &mut String, flags: &[Flag]) {
+    let labels: Vec<String> = flags
+        .iter()
+        .map(|flag| match flag.value {
+            Some(value) => format!("{} {}", flag.name, value.placeholder()),
+            None => flag.name.to_string(),
+        })
+        .collect();
+    let width = labels.iter().map(String::len).max().unwrap_or(0);
+
+    out.push_str("\nOptions:\n");
+    for (label, flag) in labels.iter().zip(flags) {
+        out.push_str(&format!("  {label:<width$}  {}\n", flag.about));
+    }
+}
+
+impl Command {
+    /// Returns the help text for the command.
+    pub fn usage(&self) -> String {
+        self.usage_for(&format!("cargo {}", self.name))
+    }
+
+    /// Returns the help text for the command, invoked as `invocation`.
+    pub fn usage_for(&self, invocation: &str) -> String {
+        let mut synopsis = invocation.to_string();
+        match self.day {
+            DayArg::New | DayArg::Required => synopsis.push_str(" <day>"),
+            DayArg::Optional => synopsis.push_str(" [<day>]"),
+            DayArg::None => {}
+        }
+        if let Some(value) = self.positional {
+            synopsis.push(' ');
+            synopsis.push_str(value.placeholder());
+        }
+        for flag in self.flags {
+            synopsis.push_str(&match flag.value {
+                Some(value) => format!(" [{} {}]", flag.name, value.placeholder()),
+                None => format!(" [{}]", flag.name),
+            });
+        }
+
+        let mut out = format!("{}\n\nUsage: {synopsis}\n", self.about);
+        let flags: Vec<Flag> = self.flags.iter().copied().chain([HELP_FLAG]).collect();
+        write_flags(&mut out, &flags);
+        out
+    }
+
+    fn flag(&self, name: &str) -> Option<&Flag> {
+        self.flags
+            .iter()
+            .chain([&HELP_FLAG])
+            .find(|flag| flag.name == name)
+    }
+}
+
+/// Returns the help text listing all commands.
+pub fn usage() -> String {
+    let width = COMMANDS.iter().map(|c| c.name.len()).max().unwrap_or(0);
+
+    let mut out = String::from(
+        "Advent of Code solutions and helpers.\n\nUsage: cargo <command> [<args>]\n\nCommands:\n",
+    );
+    for command in COMMANDS {
+        out.push_str(&format!("  {:<width$}  {}\n", command.name, command.about));
+    }
+    write_flags(&mut out, GLOBAL_FLAGS);
+    out.push_str("\nRun `cargo <command> --help` for more information on a command.\n");
+    out
+}
+
+/// An error which can be returned when parsing the command line.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ArgsError {
+    MissingCommand,
+    UnknownCommand {
+        name: String,
+        suggestion: Option<&'static str>,
+    },
+    UnknownFlag {
+        /// `None` for flags passed before a command.
+        command: Option<&'static str>,
+        flag: String,
+        suggestion: Option<&'static str>,
+    },
+    UnexpectedArgument {
+        command: &'static str,
+        argument: String,
+    },
+    InvalidArgument {
+        command: &'static str,
+        message: String,
+    },
+}
+
+impl Error for ArgsError {}
+
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgsError::MissingCommand => write!(f, "no command specified."),
+            ArgsError::UnknownCommand { name, .. } => write!(f, "unknown command \"{name}\"."),
+            ArgsError::UnknownFlag {
+                command: Some(command),
+                flag,
+                ..
+            } => write!(f, "unknown flag \"{flag}\" for command \"{command}\"."),
+            ArgsError::UnknownFlag {
+                command: None,
+                flag,
+                ..
+            } => write!(f, "unknown flag \"{flag}\"."),
+            ArgsError::UnexpectedArgument { command, argument } => {
+                write!(
+                    f,
+                    "unexpected argument \"{argument}\" for command \"{command}\"."
+                )
+            }
+            ArgsError::InvalidArgument { command, message } => {
+                write!(f, "invalid arguments for command \"{command}\": {message}.")
+            }
+        }?;
+
+        match self {
+            ArgsError::UnknownCommand {
+                suggestion: Some(suggestion),
+                ..
+            }
+            | ArgsError::UnknownFlag {
+                suggestion: Some(suggestion),
+                ..
+            } => write!(f, " Did you mean \"{suggestion}\"?")?,
+            _ => {}
+        }
+
+        match self.command() {
+            Some(command) if command == SOLUTION.name => {
+                write!(f, "\n\nRun with `--help` for usage.")
+            }
+            Some(command) => write!(f, "\n\nRun `cargo {command} --help` for usage."),
+            None => write!(
+                f,
+                "\n\nRun `cargo <command> --help` for usage, available commands: {}.",
+                COMMANDS
+                    .iter()
+                    .map(|c| c.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl ArgsError {
+    fn command(&self) -> Option<&'static str> {
+        match self {
+            ArgsError::MissingCommand | ArgsError::UnknownCommand { .. } => None,
+            ArgsError::UnknownFlag { command, .. } => *command,
+            ArgsError::UnexpectedArgument { command, .. }
+            | ArgsError::InvalidArgument { command, .. } => Some(command),
+        }
+    }
+}
+
+/// Computes the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Returns the candidate closest to `input`, if it is close enough to be a likely typo.
+pub fn suggest<'a>(input: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = (input.chars().count() / 3).max(2);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(input, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Returns an [`ArgsError::UnknownCommand`] for `name`.
+pub fn unknown_command(name: &str) -> ArgsError {
+    ArgsError::UnknownCommand {
+        name: name.to_string(),
+        suggestion: suggest(name, COMMANDS.iter().map(|command| command.name)),
+    }
+}
+
+/// Checks that all flags in `args` are known to `command`, before any positional arguments are parsed.
+/// Passing `None` checks flags that were passed without a command.
+pub fn check_flags(command: Option<&'static Command>, args: &[String]) -> Result<(), ArgsError> {
+    let flags = match command {
+        Some(command) => command.flags,
+        None => GLOBAL_FLAGS,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') {
+            continue;
+        }
+
+        // values can also be passed as `--flag=value`.
+        let (name, has_inline_value) = match arg.split_once('=') {
+            Some((name, _)) => (name, true),
+            None => (arg.as_str(), false),
+        };
+
+        let known = match command {
+            Some(command) => command.flag(name),
+            None => flags.iter().find(|flag| flag.name == name),
+        };
+
+        match known {
+            // skip the value so that values starting with a dash are not mistaken for flags.
+            Some(Flag { value: Some(_), .. }) if !has_inline_value => {
+                args.next();
+            }
+            Some(_) => {}
+            None if name == "-h" || (command.is_none() && name == "-V") => {}
+            None => {
+                return Err(ArgsError::UnknownFlag {
+                    command: command.map(|command| command.name),
+                    flag: name.into(),
+                    suggestion: suggest(name, flags.iter().chain([&HELP_FLAG]).map(|f| f.name)),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{ArgsError, COMMANDS, check_flags, find_command, suggest, unknown_command};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|x| (*x).to_string()).collect()
+    }
+
+    #[test]
+    fn suggests_close_matches() {
+        let flags = ["--release", "--dhat", "--submit"];
+        assert_eq!(suggest("--relase", flags), Some("--release"));
+        assert_eq!(suggest("--sbumit", flags), Some("--submit"));
+        assert_eq!(suggest("--foo", flags), None);
+        assert_eq!(
+            unknown_command("slove"),
+            ArgsError::UnknownCommand {
+                name: "slove".into(),
+                suggestion: Some("solve"),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_flags() {
+        let solve = find_command("solve");
+        assert!(check_flags(solve, &args(&["01", "--release", "--submit", "1"])).is_ok());
+        assert!(check_flags(solve, &args(&["01", "--help"])).is_ok());
+        assert_eq!(
+            check_flags(solve, &args(&["--relase", "01"])),
+            Err(ArgsError::UnknownFlag {
+                command: Some("solve"),
+                flag: "--relase".into(),
+                suggestion: Some("--release"),
+            })
+        );
+        assert!(check_flags(solve, &args(&["01", "--submit=1", "--release"])).is_ok());
+        assert_eq!(
+            check_flags(solve, &args(&["01", "--part=2", "--relase"])),
+            Err(ArgsError::UnknownFlag {
+                command: Some("solve"),
+                flag: "--relase".into(),
+                suggestion: Some("--release"),
+            })
+        );
+        assert!(check_flags(find_command("scaffold"), &args(&["01", "--template=grid"])).is_ok());
+        assert!(check_flags(find_command("all"), &args(&["--submit"])).is_err());
+        assert!(check_flags(None, &args(&["--version"])).is_ok());
+    }
+
+    #[test]
+    fn generates_usage_for_all_commands() {
+        for command in COMMANDS {
+            let usage = command.usage();
+            assert!(usage.contains(&format!("cargo {}", command.name)));
+            assert!(usage.contains("--help"));
+            for flag in command.flags {
+                assert!(usage.contains(flag.name));
+                assert!(usage.contains(flag.about));
+            }
+        }
+        assert!(
+            find_command("scaffold")
+                .unwrap()
+                .usage()
+                .contains("Usage: cargo scaffold <day> [--download]")
+        );
+    }
+}
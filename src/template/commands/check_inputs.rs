@@ -0,0 +1,57 @@
+use std::process::Command;
+
+use crate::template::{
+    commands::{CommandError, CommandResult},
+    config,
+};
+
+/// Fails if git tracks any input that is not encrypted, including the named inputs in day folders.
+/// Plaintext inputs that are neither tracked nor ignored are reported as a warning, since they are easy to commit by accident.
+pub fn handle() -> CommandResult {
+    let tracked = plaintext_inputs(&[])?;
+    let unignored = plaintext_inputs(&["--others", "--exclude-standard"])?;
+
+    if !unignored.is_empty() {
+        eprintln!(
+            "Warning: these inputs are not ignored by git, consider adding \"{}/**/*.txt\" to `.gitignore`:",
+            config::get().paths.inputs.display()
+        );
+        for file in &unignored {
+            eprintln!("  {file}");
+        }
+    }
+
+    if tracked.is_empty() {
+        println!("✅ No plaintext inputs are tracked by git.");
+        Ok(())
+    } else {
+        Err(CommandError::Unavailable(format!(
+            "plaintext inputs are tracked by git, encrypt them with `cargo encrypt` and remove them with `git rm --cached`:\n  {}",
+            tracked.join("\n  ")
+        )))
+    }
+}
+
+/// Lists the plaintext inputs in the inputs folder that `git ls-files` returns with the given options.
+fn plaintext_inputs(options: &[&str]) -> CommandResult<Vec<String>> {
+    let inputs = config::get().paths.inputs.clone();
+    let command = format!("git ls-files {}", options.join(" "));
+
+    let output = Command::new("git")
+        .arg("ls-files")
+        .args(options)
+        .arg("--")
+        .arg(&inputs)
+        .output()
+        .map_err(|e| CommandError::Spawn(command.clone(), e))?;
+
+    if !output.status.success() {
+        return Err(CommandError::ChildProcess(command, output.status));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|file| file.ends_with(".txt"))
+        .map(str::to_string)
+        .collect())
+}
@@ -0,0 +1,404 @@
+use std::fmt::Write;
+
+use crate::template::{
+    all_days,
+    cli::{COMMANDS, Command, DayArg, FlagValue, SHELLS},
+    commands::{CommandError, CommandResult, scaffold},
+};
+
+const BIN_NAME: &str = "advent_of_code";
+
+pub fn handle(shell: &str) -> CommandResult {
+    let script = match shell {
+        "bash" => bash(),
+        "zsh" => zsh(),
+        "fish" => fish(),
+        _ => {
+            return Err(CommandError::Unavailable(format!(
+                "unsupported shell \"{shell}\", expected one of: {}.",
+                SHELLS.join(", ")
+            )));
+        }
+    };
+
+    print!("{script}");
+    Ok(())
+}
+
+fn value_words(value: FlagValue) -> Vec<String> {
+    match value {
+        FlagValue::Part => vec!["1".into(), "2".into()],
+        FlagValue::Template => scaffold::available_templates(),
+        FlagValue::Shell => SHELLS.iter().map(|x| (*x).to_string()).collect(),
+        FlagValue::Path | FlagValue::Fps => vec![],
+        FlagValue::TimingsAction => vec!["compare".into()],
+    }
+}
+
+fn all_day_words() -> String {
+    all_days()
+        .map(|day| day.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Escapes `text` for a single-quoted zsh word.
+fn zsh_quote(text: &str) -> String {
+    text.replace('\'', r"'\''")
+}
+
+/// Escapes `text` for the description of an `_arguments` spec, which ends at the first unescaped `]`.
+fn zsh_description(text: &str) -> String {
+    zsh_quote(&text.replace('\\', r"\\").replace(']', r"\]"))
+}
+
+/// Escapes `text` for a single-quoted fish word.
+fn fish_quote(text: &str) -> String {
+    text.replace('\\', r"\\").replace('\'', r"\'")
+}
+
+fn command_names() -> String {
+    COMMANDS
+        .iter()
+        .map(|command| command.name)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn bash() -> String {
+    let mut script = String::new();
+
+    let _ = writeln!(
+        script,
+        r#"# bash completion for {BIN_NAME} and its cargo aliases.
+
+_{BIN_NAME}_days() {{
+    local f
+    for f in src/bin/[0-9][0-9].rs; do
+        [[ -e "$f" ]] && basename "$f" .rs
+    done
+}}
+
+# $1: index of the subcommand in COMP_WORDS.
+_{BIN_NAME}_complete() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local cmd="${{COMP_WORDS[$1]}}" words=""
+
+    if (( COMP_CWORD == $1 )); then
+        COMPREPLY=($(compgen -W "{commands}" -- "$cur"))
+        return
+    fi
+
+    case "$prev" in"#,
+        commands = command_names()
+    );
+
+    for value in [FlagValue::Part, FlagValue::Template] {
+        let mut flags: Vec<&str> = COMMANDS
+            .iter()
+            .flat_map(|command| command.flags)
+            .filter(|flag| flag.value == Some(value))
+            .map(|flag| flag.name)
+            .collect();
+        flags.sort_unstable();
+        flags.dedup();
+        let _ = writeln!(
+            script,
+            "        {})\n            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            return\n            ;;",
+            flags.join("|"),
+            value_words(value).join(" ")
+        );
+    }
+
+    script.push_str("    esac\n\n    case \"$cmd\" in\n");
+
+    for command in COMMANDS {
+        let mut words: Vec<String> = command.flags.iter().map(|f| f.name.into()).collect();
+        if let Some(value) = command.positional {
+            words.extend(value_words(value));
+        }
+        match command.day {
+            DayArg::New => words.push(all_day_words()),
+            DayArg::Required | DayArg::Optional => words.push(format!("$(_{BIN_NAME}_days)")),
+            DayArg::None => {}
+        }
+        let _ = writeln!(
+            script,
+            "        {})\n            words=\"{}\"\n            ;;",
+            command.name,
+            words.join(" ")
+        );
+    }
+
+    let _ = write!(
+        script,
+        r#"    esac
+
+    COMPREPLY=($(compgen -W "$words" -- "$cur"))
+}}
+
+_{BIN_NAME}() {{
+    _{BIN_NAME}_complete 1
+}}
+
+# `cargo <alias>` completions, everything else is handed to the completion that was registered for cargo before.
+_{BIN_NAME}_cargo() {{
+    if (( COMP_CWORD > 1 )); then
+        case "${{COMP_WORDS[1]}}" in
+            {aliases})
+                _{BIN_NAME}_complete 1
+                return
+                ;;
+        esac
+    fi
+
+    if [[ -n "$_{BIN_NAME}_cargo_previous" ]]; then
+        "$_{BIN_NAME}_cargo_previous" "$@"
+    fi
+}}
+
+complete -F _{BIN_NAME} {BIN_NAME}
+
+# bash-completion loads cargo's completion lazily, so load it now to be able to hand over to it.
+if ! complete -p cargo &> /dev/null; then
+    if declare -F _comp_load > /dev/null; then
+        _comp_load cargo
+    elif declare -F __load_completion > /dev/null; then
+        __load_completion cargo
+    fi
+fi
+
+_{BIN_NAME}_cargo_spec="$(complete -p cargo 2> /dev/null)"
+if [[ "$_{BIN_NAME}_cargo_spec" =~ -F\ ([^ ]+) ]]; then
+    if [[ "${{BASH_REMATCH[1]}}" != _{BIN_NAME}_cargo ]]; then
+        _{BIN_NAME}_cargo_previous="${{BASH_REMATCH[1]}}"
+    fi
+    # keep the options of the previous spec.
+    eval "${{_{BIN_NAME}_cargo_spec/-F ${{BASH_REMATCH[1]}} /-F _{BIN_NAME}_cargo }}"
+else
+    complete -o default -F _{BIN_NAME}_cargo cargo
+fi
+unset _{BIN_NAME}_cargo_spec
+"#,
+        aliases = command_names().replace(' ', "|")
+    );
+
+    script
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn zsh_command(command: &Command) -> String {
+    let mut specs: Vec<String> = command
+        .flags
+        .iter()
+        .map(|flag| match flag.value {
+            Some(value) => format!(
+                "'{}[{}]:{}:({})'",
+                flag.name,
+                zsh_description(flag.about),
+                flag.name.trim_start_matches('-'),
+                value_words(value).join(" ")
+            ),
+            None => format!("'{}[{}]'", flag.name, zsh_description(flag.about)),
+        })
+        .collect();
+
+    if let Some(value) = command.positional {
+        specs.push(format!("'1:argument:({})'", value_words(value).join(" ")));
+    }
+
+    match command.day {
+        DayArg::New => specs.push(format!("'1:day:({})'", all_day_words())),
+        DayArg::Required => specs.push(format!("'1:day:_{BIN_NAME}_days'")),
+        DayArg::Optional => specs.push(format!("'::day:_{BIN_NAME}_days'")),
+        DayArg::None => {}
+    }
+
+    if specs.is_empty() {
+        return format!("        {})\n            ;;\n", command.name);
+    }
+
+    format!(
+        "        {})\n            _arguments \\\n                {}\n            ;;\n",
+        command.name,
+        specs.join(" \\\n                ")
+    )
+}
+
+fn zsh() -> String {
+    let descriptions: Vec<String> = COMMANDS
+        .iter()
+        .map(|command| format!("        '{}:{}'", command.name, zsh_quote(command.about)))
+        .collect();
+
+    let cases: String = COMMANDS.iter().map(zsh_command).collect();
+
+    format!(
+        r#"#compdef {BIN_NAME}
+# zsh completion for {BIN_NAME} and its cargo aliases.
+
+_{BIN_NAME}_days() {{
+    local -a days
+    days=(src/bin/[0-9][0-9].rs(N:t:r))
+    _describe 'day' days
+}}
+
+_{BIN_NAME}() {{
+    local -a commands
+    commands=(
+{descriptions}
+    )
+
+    if (( CURRENT == 2 )); then
+        _describe 'command' commands
+        return
+    fi
+
+    local cmd="$words[2]"
+    shift words
+    (( CURRENT-- ))
+
+    case "$cmd" in
+{cases}    esac
+}}
+
+# `cargo <alias>` completions, everything else is handed to the completion that was registered for cargo before.
+_{BIN_NAME}_cargo() {{
+    if (( CURRENT > 2 )) && (( ${{{BIN_NAME}_aliases[(Ie)$words[2]]}} )); then
+        _{BIN_NAME}
+    else
+        "${{_{BIN_NAME}_cargo_previous:-_default}}" "$@"
+    fi
+}}
+
+typeset -ga {BIN_NAME}_aliases
+{BIN_NAME}_aliases=({aliases})
+
+if [[ "${{_comps[cargo]}}" != _{BIN_NAME}_cargo ]]; then
+    typeset -g _{BIN_NAME}_cargo_previous="${{_comps[cargo]}}"
+fi
+
+compdef _{BIN_NAME} {BIN_NAME}
+compdef _{BIN_NAME}_cargo cargo
+"#,
+        descriptions = descriptions.join("\n"),
+        aliases = command_names()
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn fish() -> String {
+    let mut script = format!(
+        r#"# fish completion for {BIN_NAME} and its cargo aliases.
+
+function __{BIN_NAME}_days
+    for f in src/bin/[0-9][0-9].rs
+        basename $f .rs
+    end
+end
+
+function __{BIN_NAME}_needs_command
+    test (count (commandline -opc)) -eq 1
+end
+
+function __{BIN_NAME}_using_command
+    set -l cmd (commandline -opc)
+    test (count $cmd) -ge 2; and test "$cmd[2]" = "$argv[1]"
+end
+
+complete -c {BIN_NAME} -f
+"#
+    );
+
+    for command in COMMANDS {
+        let _ = writeln!(
+            script,
+            "complete -c {BIN_NAME} -n __{BIN_NAME}_needs_command -a {} -d '{}'",
+            command.name,
+            fish_quote(command.about)
+        );
+    }
+
+    // the same completions apply to `advent_of_code <command>` and `cargo <alias>`.
+    for bin in [BIN_NAME, "cargo"] {
+        script.push('\n');
+        for command in COMMANDS {
+            let condition = format!("'__{BIN_NAME}_using_command {}'", command.name);
+
+            for flag in command.flags {
+                let values = flag.value.map_or_else(String::new, |value| {
+                    format!(" -x -a '{}'", value_words(value).join(" "))
+                });
+                let _ = writeln!(
+                    script,
+                    "complete -c {bin} -n {condition} -l {}{values} -d '{}'",
+                    flag.name.trim_start_matches('-'),
+                    fish_quote(flag.about)
+                );
+            }
+
+            let args = match (command.day, command.positional) {
+                (DayArg::New, _) => Some(all_day_words()),
+                (DayArg::Required | DayArg::Optional, _) => Some(format!("(__{BIN_NAME}_days)")),
+                (DayArg::None, Some(value)) => Some(value_words(value).join(" ")),
+                (DayArg::None, None) => None,
+            };
+
+            if let Some(args) = args {
+                let _ = writeln!(script, "complete -c {bin} -n {condition} -f -a '{args}'");
+            }
+        }
+    }
+
+    script
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{bash, fish, fish_quote, handle, zsh, zsh_description, zsh_quote};
+    use crate::template::{cli::COMMANDS, commands::CommandError};
+
+    #[test]
+    fn completes_all_commands_and_flags() {
+        for script in [bash(), zsh(), fish()] {
+            for command in COMMANDS {
+                assert!(script.contains(command.name));
+                for flag in command.flags {
+                    assert!(
+                        script.contains(flag.name.trim_start_matches('-')),
+                        "missing {}",
+                        flag.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn errors_for_unsupported_shells() {
+        assert!(matches!(
+            handle("powershell"),
+            Err(CommandError::Unavailable(_))
+        ));
+    }
+
+    #[test]
+    fn completes_days_from_bin_folder() {
+        assert!(bash().contains("src/bin/[0-9][0-9].rs"));
+        assert!(zsh().contains("src/bin/[0-9][0-9].rs"));
+        assert!(fish().contains("src/bin/[0-9][0-9].rs"));
+    }
+
+    #[test]
+    fn escapes_descriptions() {
+        assert_eq!(zsh_quote("the day's input"), r"the day'\''s input");
+        assert_eq!(zsh_description("a [b] c"), r"a [b\] c");
+        assert_eq!(fish_quote(r"it's a \ b"), r"it\'s a \\ b");
+    }
+}
@@ -0,0 +1,41 @@
+use std::fs;
+
+use crate::template::{
+    commands::{CommandError, CommandResult, encrypt::input_files},
+    crypto::{ENCRYPTED_EXTENSION, Key},
+};
+
+/// Decrypts all encrypted inputs next to their encrypted counterpart. Returns the number of decrypted files.
+/// Inputs that already exist with different contents are kept as-is.
+pub fn handle() -> CommandResult<usize> {
+    let key = Key::read(&Key::path())?;
+    let mut count = 0;
+
+    for path in input_files(&format!(".{ENCRYPTED_EXTENSION}"))? {
+        let data = fs::read(&path).map_err(|e| CommandError::IO(path.clone(), e))?;
+        let plaintext = key
+            .decrypt(&data)
+            .map_err(|e| CommandError::Unavailable(format!("\"{}\": {e}", path.display())))?;
+
+        let target = path.with_extension("");
+        match fs::read(&target) {
+            Ok(existing) if existing == plaintext => continue,
+            Ok(existing) if !existing.trim_ascii().is_empty() => {
+                eprintln!(
+                    "Warning: \"{}\" differs from its encrypted version, skipping it.",
+                    target.display()
+                );
+                continue;
+            }
+            _ => {}
+        }
+
+        fs::write(&target, plaintext).map_err(|e| CommandError::IO(target.clone(), e))?;
+        println!("Decrypted \"{}\".", target.display());
+        count += 1;
+    }
+
+    println!("---");
+    println!("🔓 Decrypted {count} input(s).");
+    Ok(count)
+}
@@ -0,0 +1,84 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{
+    commands::{CommandError, CommandResult},
+    config,
+    crypto::{Key, encrypted_path},
+};
+
+/// Encrypts all inputs to `<input>.enc`, creating a key first if there is none. Returns the number of encrypted files.
+pub fn handle() -> CommandResult<usize> {
+    let key_path = Key::path();
+    let key = if key_path.exists() {
+        Key::read(&key_path)?
+    } else {
+        let key = Key::generate();
+        key.write(&key_path)?;
+        println!("🔑 Created a new key at \"{}\".", key_path.display());
+        println!("   Back it up, inputs cannot be decrypted without it.");
+        key
+    };
+
+    let mut count = 0;
+
+    for path in input_files(".txt")? {
+        let plaintext = fs::read(&path).map_err(|e| CommandError::IO(path.clone(), e))?;
+        // inputs that were scaffolded, but not downloaded yet are not worth encrypting.
+        if plaintext.trim_ascii().is_empty() {
+            continue;
+        }
+
+        // keep existing files if they are up-to-date, so that they do not show up as changed in git.
+        let target = encrypted_path(&path);
+        let is_current = fs::read(&target)
+            .ok()
+            .and_then(|data| key.decrypt(&data).ok())
+            .is_some_and(|existing| existing == plaintext);
+        if is_current {
+            continue;
+        }
+
+        fs::write(&target, key.encrypt(&plaintext))
+            .map_err(|e| CommandError::IO(target.clone(), e))?;
+        println!("Encrypted \"{}\".", path.display());
+        count += 1;
+    }
+
+    println!("---");
+    println!("🔒 Encrypted {count} input(s).");
+    Ok(count)
+}
+
+/// Lists the files in the inputs folder and its day folders whose name ends with `suffix`, sorted by path.
+pub(super) fn input_files(suffix: &str) -> CommandResult<Vec<PathBuf>> {
+    let dir: &Path = &config::get().paths.inputs;
+    let mut paths = vec![];
+
+    for path in read_dir(dir)? {
+        if path.is_dir() {
+            paths.extend(read_dir(&path)?);
+        } else {
+            paths.push(path);
+        }
+    }
+
+    paths.retain(|path| {
+        path.is_file()
+            && path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(suffix))
+    });
+    paths.sort();
+    Ok(paths)
+}
+
+fn read_dir(dir: &Path) -> CommandResult<Vec<PathBuf>> {
+    let entries = fs::read_dir(dir).map_err(|e| CommandError::IO(dir.to_path_buf(), e))?;
+    Ok(entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .collect())
+}
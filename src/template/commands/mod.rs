@@ -1,6 +1,95 @@
+use std::{error::Error, fmt::Display, io, path::PathBuf, process::ExitStatus};
+
+use crate::template::{aoc_cli::AocCommandError, config, readme_benchmarks, run_multi};
+
 pub mod all;
+#[cfg(feature = "encrypted-inputs")]
+pub mod check_inputs;
+pub mod completions;
+#[cfg(feature = "encrypted-inputs")]
+pub mod decrypt;
 pub mod download;
+#[cfg(feature = "encrypted-inputs")]
+pub mod encrypt;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod timings;
+#[cfg(feature = "today")]
+pub mod today;
+pub mod verify;
+
+/// An error which can be returned by any of the commands.
+/// Commands never exit the process themselves, mapping errors to exit codes is up to the caller.
+#[derive(Debug)]
+pub enum CommandError {
+    /// Calling aoc-cli failed.
+    AocCli(AocCommandError),
+    /// A file could not be read or written.
+    IO(PathBuf, io::Error),
+    /// A file or the output of a command could not be parsed.
+    Parse(String),
+    /// A child process could not be run.
+    Spawn(String, io::Error),
+    /// A child process exited with a non-zero status.
+    ChildProcess(String, ExitStatus),
+    /// The command cannot run with the given arguments or in the current state of the project.
+    Unavailable(String),
+}
+
+/// The result type returned by all commands.
+pub type CommandResult<T = ()> = Result<T, CommandError>;
+
+impl Error for CommandError {}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandError::AocCli(e) => write!(f, "{e}"),
+            CommandError::IO(path, e) => write!(f, "could not access \"{}\": {e}", path.display()),
+            CommandError::Parse(message) | CommandError::Unavailable(message) => {
+                write!(f, "{message}")
+            }
+            CommandError::Spawn(command, e) => write!(f, "could not run `{command}`: {e}"),
+            CommandError::ChildProcess(command, status) => {
+                write!(f, "`{command}` failed with {status}.")
+            }
+        }
+    }
+}
+
+impl From<AocCommandError> for CommandError {
+    fn from(e: AocCommandError) -> Self {
+        CommandError::AocCli(e)
+    }
+}
+
+impl From<readme_benchmarks::Error> for CommandError {
+    fn from(e: readme_benchmarks::Error) -> Self {
+        match e {
+            readme_benchmarks::Error::Parser(message) => CommandError::Parse(message),
+            readme_benchmarks::Error::IO(e) => CommandError::IO(config::get().readme.clone(), e),
+        }
+    }
+}
+
+impl From<run_multi::Error> for CommandError {
+    fn from(e: run_multi::Error) -> Self {
+        let e = match e {
+            run_multi::Error::BrokenPipe => io::ErrorKind::BrokenPipe.into(),
+            run_multi::Error::IO(e) => e,
+        };
+        CommandError::Spawn("cargo run".into(), e)
+    }
+}
+
+#[cfg(feature = "encrypted-inputs")]
+impl From<crate::template::crypto::CryptoError> for CommandError {
+    fn from(e: crate::template::crypto::CryptoError) -> Self {
+        match e {
+            crate::template::crypto::CryptoError::IO(path, e) => CommandError::IO(path, e),
+            e => CommandError::Unavailable(e.to_string()),
+        }
+    }
+}
@@ -1,17 +1,44 @@
-use std::process;
+use crate::template::{
+    Day, aoc_cli,
+    commands::{CommandError, CommandResult},
+    markdown::{render, terminal_width},
+    puzzle::{Puzzle, part_markdown, read_markdown},
+};
 
-use crate::template::{Day, aoc_cli};
+/// Renders the puzzle description, or only the given part of it.
+pub fn handle(day: Day, part: Option<u8>) -> CommandResult {
+    let cached = Puzzle::read(day);
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
+    // only hit the network if the description is missing or part two might have been unlocked since.
+    let needs_download = match &cached {
+        Some(puzzle) => part != Some(1) && !puzzle.has_part_two(),
+        None => true,
+    };
+
+    if needs_download {
+        match aoc_cli::check() {
+            Ok(()) => {
+                // a stale cached description is still worth showing if the refresh fails.
+                if let Err(e) = aoc_cli::download_puzzle(day) {
+                    eprintln!("failed to call aoc-cli: {e}");
+                }
+            }
+            Err(e) if cached.is_none() => return Err(e.into()),
+            Err(_) => {}
+        }
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let markdown = read_markdown(day).ok_or_else(|| {
+        CommandError::Unavailable(format!("could not read puzzle description for day {day}."))
+    })?;
+
+    let markdown = match part {
+        Some(part) => part_markdown(&markdown, part).ok_or_else(|| {
+            CommandError::Unavailable(format!("part {part} of day {day} is not available yet."))
+        })?,
+        None => markdown,
     };
+
+    println!("{}", render(&markdown, terminal_width()));
+    Ok(())
 }
@@ -1,13 +1,127 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
 };
 
-use crate::template::Day;
+use crate::template::{
+    Day, aoc_cli,
+    commands::{CommandError, CommandResult},
+    config,
+    puzzle::Puzzle,
+};
+
+macro_rules! builtin_template {
+    ($name:literal) => {
+        (
+            $name,
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/templates/",
+                $name,
+                ".txt"
+            )),
+        )
+    };
+}
+
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    builtin_template!("default"),
+    builtin_template!("grid"),
+    builtin_template!("graph"),
+    builtin_template!("parse-struct"),
+    builtin_template!("solution-trait"),
+];
+
+/// Values that are substituted into a module template.
+struct TemplateVars {
+    day: Day,
+    year: Option<u16>,
+    title: Option<String>,
+    example_answers: [Option<String>; 2],
+    example_path: String,
+}
+
+impl TemplateVars {
+    fn new(day: Day, example_path: &str) -> Self {
+        let puzzle = Puzzle::read(day).unwrap_or_default();
+        Self {
+            day,
+            year: aoc_cli::get_year(),
+            example_answers: [puzzle.example_answer(1), puzzle.example_answer(2)],
+            title: puzzle.title,
+            example_path: example_path.into(),
+        }
+    }
+}
+
+fn render_template(template: &str, vars: &TemplateVars) -> String {
+    let answer = |answer: &Option<String>| {
+        answer
+            .as_ref()
+            .map_or_else(|| "None".into(), |x| format!("Some({x})"))
+    };
+
+    template
+        .replace("%DAY_NUMBER%", &vars.day.into_inner().to_string())
+        .replace("%DAY_PADDED%", &vars.day.to_string())
+        .replace(
+            "%YEAR%",
+            &vars.year.map(|x| x.to_string()).unwrap_or_default(),
+        )
+        .replace(
+            "%PUZZLE_TITLE%",
+            vars.title.as_deref().unwrap_or("Untitled"),
+        )
+        .replace("%EXAMPLE_PART_ONE%", &answer(&vars.example_answers[0]))
+        .replace("%EXAMPLE_PART_TWO%", &answer(&vars.example_answers[1]))
+        .replace("%EXAMPLE_PATH%", &vars.example_path)
+}
+
+/// Looks up a template by name, preferring project-level templates over built-in ones.
+/// A `<name>.txt` file in the configured templates folder takes precedence over the built-in template of the same name.
+fn load_template(name: &str) -> CommandResult<String> {
+    let path = config::get().paths.templates.join(format!("{name}.txt"));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => return Ok(template),
+        Err(e) if e.kind() != ErrorKind::NotFound => return Err(CommandError::IO(path, e)),
+        Err(_) => {}
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| {
+            CommandError::Unavailable(format!(
+                "unknown template \"{name}\", available templates: {}.",
+                available_templates().join(", ")
+            ))
+        })
+}
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Names of all built-in and project-level templates.
+pub fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(&config::get().paths.templates) {
+        names.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        }));
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -27,53 +141,237 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// The outcome of scaffolding an input or example file.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DataFileStatus {
+    Created,
+    Skipped,
+    Replaced { backup_path: String },
+}
+
+/// Returns the first unused backup path for `path`, i.e. `<path>.bak`, `<path>.bak.1`, ...
+fn get_backup_path(path: &str) -> String {
+    let mut backup_path = format!("{path}.bak");
+    let mut i = 1;
+    while Path::new(&backup_path).exists() {
+        backup_path = format!("{path}.bak.{i}");
+        i += 1;
+    }
+    backup_path
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+/// Creates an empty data file. Existing non-empty files are left untouched unless `overwrite_data` is set,
+/// in which case their contents are backed up before they are truncated.
+fn create_data_file(path: &str, overwrite_data: bool) -> Result<DataFileStatus, std::io::Error> {
+    let is_empty = match fs::metadata(path) {
+        Ok(metadata) => metadata.len() == 0,
+        Err(e) if e.kind() == ErrorKind::NotFound => true,
+        Err(e) => return Err(e),
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    if is_empty {
+        create_file(path)?;
+        return Ok(DataFileStatus::Created);
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    if !overwrite_data {
+        return Ok(DataFileStatus::Skipped);
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    let backup_path = get_backup_path(path);
+    fs::copy(path, &backup_path)?;
+    create_file(path)?;
+    Ok(DataFileStatus::Replaced { backup_path })
+}
+
+fn scaffold_data_file(
+    path: &str,
+    kind: &str,
+    overwrite_data: bool,
+) -> CommandResult<DataFileStatus> {
+    let status =
+        create_data_file(path, overwrite_data).map_err(|e| CommandError::IO(path.into(), e))?;
+
+    match &status {
+        DataFileStatus::Created => {
+            println!("Created empty {kind} file \"{path}\"");
+        }
+        DataFileStatus::Skipped => {
+            println!(
+                "Skipped {kind} file \"{path}\" as it is not empty. Use `--overwrite-data` to replace it."
+            );
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+        DataFileStatus::Replaced { backup_path } => {
+            println!(
+                "Backed up {kind} file \"{path}\" to \"{backup_path}\" and created an empty one"
+            );
         }
     }
 
+    Ok(status)
+}
+
+/// Whether the input is stored encrypted next to `path`, see the `encrypt` command.
+/// Checked regardless of the `encrypted-inputs` feature, as `scaffold` runs without it.
+fn is_input_encrypted(path: &str) -> bool {
+    Path::new(&format!("{path}.enc")).exists()
+}
+
+/// The path of the solution module of `day`.
+pub fn module_path(day: Day) -> String {
+    format!("src/bin/{day}.rs")
+}
+
+/// The files created by [`handle`].
+#[derive(Debug)]
+pub struct Scaffolded {
+    pub module_path: PathBuf,
+    pub input: DataFileStatus,
+    pub example: DataFileStatus,
+}
+
+pub fn handle(
+    day: Day,
+    overwrite: bool,
+    overwrite_data: bool,
+    template: Option<&str>,
+) -> CommandResult<Scaffolded> {
+    let config = config::get();
+    let input_path = config.paths.inputs.join(format!("{day}.txt"));
+    let input_path = input_path.display().to_string();
+    let example_path = config.paths.examples.join(format!("{day}.txt"));
+    let example_path = example_path.display().to_string();
+    let module_path = module_path(day);
+
+    let template = load_template(template.unwrap_or(&config.default_template))?;
+
+    let mut file = safe_create_file(&module_path, overwrite)
+        .map_err(|e| CommandError::IO(module_path.clone().into(), e))?;
+
+    let vars = TemplateVars::new(day, &example_path);
+
+    file.write_all(render_template(&template, &vars).as_bytes())
+        .map_err(|e| CommandError::IO(module_path.clone().into(), e))?;
+    println!("Created module file \"{}\"", &module_path);
+
+    let input = if is_input_encrypted(&input_path) {
+        println!("Skipped input file \"{input_path}\" as it is stored encrypted");
+        DataFileStatus::Skipped
+    } else {
+        scaffold_data_file(&input_path, "input", overwrite_data)?
+    };
+    let example = scaffold_data_file(&example_path, "example", overwrite_data)?;
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
+
+    Ok(Scaffolded {
+        module_path: module_path.into(),
+        input,
+        example,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{
+        BUILTIN_TEMPLATES, DataFileStatus, TemplateVars, create_data_file, handle,
+        is_input_encrypted, render_template,
+    };
+    use crate::{day, template::commands::CommandError};
+    use std::{env, fs};
+
+    fn get_mock_vars() -> TemplateVars {
+        TemplateVars {
+            day: day!(5),
+            year: Some(2025),
+            title: Some("Cafeteria".into()),
+            example_answers: [Some("3".into()), None],
+            example_path: "data/examples/05.txt".into(),
+        }
+    }
+
+    #[test]
+    fn renders_all_variables() {
+        let rendered = render_template(
+            "%YEAR% %DAY_NUMBER% %DAY_PADDED% %PUZZLE_TITLE% %EXAMPLE_PART_ONE% %EXAMPLE_PART_TWO% %EXAMPLE_PATH%",
+            &get_mock_vars(),
+        );
+        assert_eq!(
+            rendered,
+            "2025 5 05 Cafeteria Some(3) None data/examples/05.txt"
+        );
+    }
+
+    #[test]
+    fn renders_builtin_templates_completely() {
+        for (name, template) in BUILTIN_TEMPLATES {
+            let rendered = render_template(template, &get_mock_vars());
+            assert!(!rendered.contains('%'), "unrendered variable in {name}");
+        }
+    }
+
+    fn get_tmp_path(name: &str) -> String {
+        let dir = env::temp_dir().join("aoc-scaffold-tests");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(path.with_extension("txt.bak"));
+        path.to_string_lossy().into()
+    }
+
+    #[test]
+    fn creates_missing_data_files() {
+        let path = get_tmp_path("missing.txt");
+        assert_eq!(
+            create_data_file(&path, false).unwrap(),
+            DataFileStatus::Created
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+    }
+
+    #[test]
+    fn keeps_non_empty_data_files() {
+        let path = get_tmp_path("existing.txt");
+        fs::write(&path, "1,2,3").unwrap();
+        assert_eq!(
+            create_data_file(&path, false).unwrap(),
+            DataFileStatus::Skipped
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1,2,3");
+    }
+
+    #[test]
+    fn backs_up_replaced_data_files() {
+        let path = get_tmp_path("replaced.txt");
+        fs::write(&path, "1,2,3").unwrap();
+        let backup_path = format!("{path}.bak");
+        assert_eq!(
+            create_data_file(&path, true).unwrap(),
+            DataFileStatus::Replaced {
+                backup_path: backup_path.clone()
+            }
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        assert_eq!(fs::read_to_string(&backup_path).unwrap(), "1,2,3");
+    }
+
+    #[test]
+    fn detects_encrypted_inputs() {
+        let path = get_tmp_path("encrypted.txt");
+        let encrypted_path = format!("{path}.enc");
+        let _ = fs::remove_file(&encrypted_path);
+        assert!(!is_input_encrypted(&path));
+
+        fs::write(&encrypted_path, "").unwrap();
+        assert!(is_input_encrypted(&path));
+    }
+
+    #[test]
+    fn errors_for_unknown_templates() {
+        let result = handle(day!(25), false, false, Some("does-not-exist"));
+        assert!(matches!(result, Err(CommandError::Unavailable(_))));
+    }
 }
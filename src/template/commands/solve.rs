@@ -1,8 +1,20 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{
+    Day,
+    commands::{CommandError, CommandResult},
+    runner::{self, STACK_SIZE_HINT},
+    visualize,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    part: Option<u8>,
+    visualize: Option<visualize::Settings>,
+) -> CommandResult {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -18,17 +30,46 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    if let Some(settings) = visualize {
+        cmd_args.push("--visualize".to_string());
+        cmd_args.push("--fps".to_string());
+        cmd_args.push(settings.fps.to_string());
+
+        if let Some(frames) = settings.frames {
+            cmd_args.push("--frames".to_string());
+            cmd_args.push(frames.display().to_string());
+        }
+    }
+
+    let command = format!("cargo {}", cmd_args.join(" "));
+
+    let status = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(|e| CommandError::Spawn(command.clone(), e))?;
+
+    if status.success() {
+        return Ok(());
+    }
 
-    cmd.wait().unwrap();
+    // stderr is not captured here, so the overflow message itself cannot be checked.
+    if runner::may_have_overflowed(status) {
+        eprintln!(
+            "Day {day} was aborted, e.g. by a stack overflow. {STACK_SIZE_HINT} \
+            Set `stack.day_{day}` to only change it for this day."
+        );
+    }
+    Err(CommandError::ChildProcess(command, status))
 }
@@ -1,10 +1,34 @@
 use std::collections::HashSet;
+use std::process::{Command, Stdio};
 
+use crate::template::commands::{CommandError, CommandResult};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{Day, all_days, config, readme_benchmarks};
+
+/// Benchmarks the selected days and returns their timings. If `part` is set, only that part is benchmarked.
+/// With `scale`, the day is timed on generated inputs instead, see [`scale`](crate::template::scale), and no timings are returned.
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    part: Option<u8>,
+    scale: bool,
+) -> CommandResult<Timings> {
+    if scale {
+        let Some(day) = day else {
+            return Err(CommandError::Unavailable(
+                "`--scale` needs a day, e.g. `cargo time 08 --scale`.".into(),
+            ));
+        };
+        if run_all || store {
+            return Err(CommandError::Unavailable(
+                "`--scale` cannot be combined with `--all` or `--store`.".into(),
+            ));
+        }
+        return run_scale(day, part).map(|()| Timings::default());
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,20 +45,51 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, part)?.unwrap_or_default();
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let merged_timings = stored_timings.merge(&timings, part);
+        merged_timings
+            .store_file()
+            .map_err(|e| CommandError::IO(config::get().paths.timings.clone(), e))?;
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
-            }
-        }
+        readme_benchmarks::update(merged_timings)?;
+        println!("Stored updated benchmarks.");
+    }
+
+    Ok(timings)
+}
+
+/// Runs the solution of a day with `--scale`, passing its output through.
+fn run_scale(day: Day, part: Option<u8>) -> CommandResult {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--scale".to_string(),
+    ];
+
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    let command = format!("cargo {}", cmd_args.join(" "));
+
+    let status = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|e| CommandError::Spawn(command.clone(), e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(CommandError::ChildProcess(command, status))
     }
 }
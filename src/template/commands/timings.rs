@@ -0,0 +1,272 @@
+use std::{fmt::Write, path::PathBuf, time::Duration};
+
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, Day,
+    commands::{CommandError, CommandResult},
+    timings::{Machine, Timing, Timings, parse_duration},
+};
+
+/// Compares timing files, e.g. of different machines, part by part.
+pub fn compare(paths: &[PathBuf]) -> CommandResult {
+    if paths.len() < 2 {
+        return Err(CommandError::Unavailable(
+            "expected at least two timing files to compare.".into(),
+        ));
+    }
+
+    let mut runs = vec![];
+    for path in paths {
+        let timings = Timings::read(path)
+            .map_err(|e| CommandError::Parse(format!("\"{}\": {e}", path.display())))?;
+        runs.push((path.display().to_string(), timings));
+    }
+
+    print!("{}", render_comparison(&runs));
+    Ok(())
+}
+
+/// A row of the comparison: its label, and the time and implementation name of each run.
+type Row<'a> = (String, Vec<Option<f64>>, Vec<Option<&'a str>>);
+
+/// Renders a table of times per day and part, with the fastest run of each part highlighted and
+/// the others shown as multiples of it, followed by the machines each run was recorded on.
+/// Parts with several implementations show the name of the one that was timed.
+/// Only parts that every run has timings for are ranked, as the others cannot be compared.
+fn render_comparison(runs: &[(String, Timings)]) -> String {
+    let mut days: Vec<Day> = runs
+        .iter()
+        .flat_map(|(_, timings)| timings.data.iter().map(|t| t.day))
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let mut rows: Vec<Row> = days
+        .iter()
+        .flat_map(|day| {
+            [1, 2].map(|part| {
+                let timings: Vec<Option<&Timing>> = runs
+                    .iter()
+                    .map(|(_, timings)| timings.data.iter().find(|t| t.day == *day))
+                    .collect();
+                let nanos: Vec<Option<f64>> = timings
+                    .iter()
+                    .map(|timing| {
+                        let timing = (*timing)?;
+                        let duration = if part == 1 {
+                            &timing.part_1
+                        } else {
+                            &timing.part_2
+                        };
+                        duration.as_deref().and_then(parse_duration)
+                    })
+                    .collect();
+                let implementations = timings
+                    .iter()
+                    .map(|timing| timing.and_then(|t| t.implementations[part - 1].as_deref()))
+                    .collect();
+                (format!("{day}/{part}"), nanos, implementations)
+            })
+        })
+        .filter(|(_, nanos, _)| nanos.iter().any(Option::is_some))
+        .collect();
+
+    let is_comparable = |nanos: &[Option<f64>]| nanos.iter().all(Option::is_some);
+    let comparable = rows.iter().filter(|(_, nanos, _)| is_comparable(nanos));
+
+    let mut wins = vec![0; runs.len()];
+    for (_, nanos, _) in comparable.clone() {
+        if let Some(i) = fastest(nanos) {
+            wins[i] += 1;
+        }
+    }
+
+    let totals = (0..runs.len())
+        .map(|i| {
+            comparable
+                .clone()
+                .map(|(_, nanos, _)| nanos[i])
+                .reduce(|a, b| Some(a? + b?))
+                .flatten()
+        })
+        .collect();
+    rows.push(("Total".into(), totals, vec![None; runs.len()]));
+
+    let label_width = rows
+        .iter()
+        .map(|(label, _, _)| label.len())
+        .max()
+        .unwrap_or(0);
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|(_, nanos, implementations)| {
+            let best = fastest(nanos)
+                .filter(|_| is_comparable(nanos))
+                .and_then(|i| nanos[i]);
+            nanos
+                .iter()
+                .zip(implementations)
+                .map(|(x, implementation)| {
+                    let cell = match (x, best) {
+                        (Some(x), Some(best)) if *x == best => format!("{} ★", format_nanos(*x)),
+                        (Some(x), Some(best)) => {
+                            format!("{} ({:.2}x)", format_nanos(*x), x / best)
+                        }
+                        (Some(x), None) => format_nanos(*x),
+                        (None, _) => return "-".into(),
+                    };
+                    match implementation {
+                        Some(name) => format!("{cell} ({name})"),
+                        None => cell,
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = runs
+        .iter()
+        .enumerate()
+        .map(|(i, (name, _))| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain([name.chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut s = format!("{ANSI_BOLD}{:label_width$}{ANSI_RESET}", "Part");
+    for ((name, _), width) in runs.iter().zip(&widths) {
+        let _ = write!(s, " | {ANSI_BOLD}{name:width$}{ANSI_RESET}");
+    }
+    s.push('\n');
+
+    for ((label, _, _), row) in rows.iter().zip(&cells) {
+        let _ = write!(s, "{label:label_width$}");
+        for (cell, width) in row.iter().zip(&widths) {
+            if cell.contains('★') {
+                let _ = write!(s, " | {ANSI_BOLD}{cell:width$}{ANSI_RESET}");
+            } else {
+                let _ = write!(s, " | {cell:width$}");
+            }
+        }
+        s.push('\n');
+    }
+
+    s.push_str("\n★ fastest, the others are shown as multiples of it. Only parts that all files have are ranked and included in the total.\n");
+
+    for ((name, timings), wins) in runs.iter().zip(wins) {
+        let mut machines: Vec<&Machine> = vec![];
+        for machine in timings
+            .data
+            .iter()
+            .flat_map(|t| t.machines.iter().flatten())
+        {
+            if !machines.contains(&machine) {
+                machines.push(machine);
+            }
+        }
+
+        let _ = writeln!(
+            s,
+            "\n{ANSI_BOLD}{name}{ANSI_RESET}: fastest on {wins} part(s)"
+        );
+        if machines.is_empty() {
+            s.push_str("  unknown machine\n");
+        }
+        for machine in machines {
+            let _ = writeln!(s, "  {}", machine.summary());
+        }
+    }
+
+    s
+}
+
+/// Returns the index of the fastest run, if any run has a timing.
+fn fastest(nanos: &[Option<f64>]) -> Option<usize> {
+    nanos
+        .iter()
+        .enumerate()
+        .filter_map(|(i, x)| Some((i, (*x)?)))
+        .filter(|(_, x)| *x > 0_f64)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(i, _)| i)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.2?}", Duration::from_nanos(nanos as u64))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::render_comparison;
+    use crate::template::{
+        ANSI_BOLD, ANSI_RESET, Day,
+        timings::{Machine, Timing, Timings},
+    };
+
+    type Part<'a> = Option<(&'a str, Option<&'a str>)>;
+
+    /// Builds timings from `(day, part_1, part_2)`, where a part is its duration and the name of
+    /// the implementation that was timed.
+    fn timings(data: &[(u8, Part, Part)], machine: Option<Machine>) -> Timings {
+        Timings {
+            data: data
+                .iter()
+                .map(|(day, part_1, part_2)| Timing {
+                    day: Day::new(*day).unwrap(),
+                    part_1: part_1.map(|(duration, _)| duration.into()),
+                    part_2: part_2.map(|(duration, _)| duration.into()),
+                    total_nanos: 0.0,
+                    machines: [machine.clone(), machine.clone()],
+                    implementations: [part_1, part_2]
+                        .map(|part| part.and_then(|(_, name)| name).map(Into::into)),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn renders_ratios_to_the_fastest_run() {
+        let machine = Machine {
+            id: "0123456789abcdef".into(),
+            cpu: Some("AMD Ryzen 7 5800X".into()),
+            rustc: None,
+            profile: "release".into(),
+        };
+        let runs = vec![
+            (
+                "a.json".into(),
+                timings(
+                    &[
+                        (1, Some(("2ms", Some("v1"))), Some(("1ms", None))),
+                        (2, Some(("1µs", None)), None),
+                    ],
+                    Some(machine),
+                ),
+            ),
+            (
+                "b.json".into(),
+                timings(&[(1, Some(("1ms", Some("v2"))), None)], None),
+            ),
+        ];
+
+        let (b, r) = (ANSI_BOLD, ANSI_RESET);
+        assert_eq!(
+            render_comparison(&runs),
+            format!(
+                "{b}Part {r} | {b}a.json             {r} | {b}b.json       {r}\n\
+                01/1  | 2.00ms (2.00x) (v1) | {b}1.00ms ★ (v2){r}\n\
+                01/2  | 1.00ms              | -            \n\
+                02/1  | 1.00µs              | -            \n\
+                Total | 2.00ms (2.00x)      | {b}1.00ms ★     {r}\n\
+                \n★ fastest, the others are shown as multiples of it. Only parts that all files have are ranked and included in the total.\n\
+                \n{b}a.json{r}: fastest on 0 part(s)\n  01234567 · AMD Ryzen 7 5800X · release\n\
+                \n{b}b.json{r}: fastest on 1 part(s)\n  unknown machine\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    io::{Write, stdout},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Datelike, FixedOffset, Utc};
+
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, Day, aoc_cli,
+    commands::{CommandError, CommandResult, download, read, scaffold},
+};
+
+/// How often `--wait` retries the download while the input is not available yet.
+const DOWNLOAD_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+const DOWNLOAD_MAX_ATTEMPTS: u32 = 60;
+
+/// Scaffolds, downloads and reads the current day. Returns the day that was set up.
+pub fn handle(wait: bool) -> CommandResult<Day> {
+    if wait {
+        return handle_wait();
+    }
+
+    let day = Day::today().ok_or_else(|| {
+        CommandError::Unavailable(
+            "`today` command can only be run during the event in december. \
+            Please use `scaffold` with a specific day."
+                .into(),
+        )
+    })?;
+
+    scaffold::handle(day, false, false, None)?;
+    download::handle(day)?;
+    read::handle(day, None)?;
+    Ok(day)
+}
+
+/// Waits for the next puzzle to unlock, then scaffolds, downloads and reads it.
+fn handle_wait() -> CommandResult<Day> {
+    aoc_cli::check()?;
+
+    let (day, unlock) = Day::next_unlock().ok_or_else(|| {
+        CommandError::Unavailable("could not determine the next unlock time.".into())
+    })?;
+
+    // downloads are for the configured year, which would be the wrong puzzle.
+    if let Some(year) = aoc_cli::get_year().filter(|year| i32::from(*year) != unlock.year()) {
+        return Err(CommandError::Unavailable(format!(
+            "day {day} unlocks in {}, but the configured year is {year}. \
+            Update `AOC_YEAR` or `year` in `aoc.toml` to wait for it.",
+            unlock.year()
+        )));
+    }
+
+    countdown(day, unlock);
+
+    let module_path = scaffold::module_path(day);
+    if Path::new(&module_path).exists() {
+        println!("Module file \"{module_path}\" exists already, skipped scaffolding.");
+    } else {
+        scaffold::handle(day, false, false, None)?;
+    }
+
+    // the puzzle might not be served yet right at midnight, so keep trying for a while.
+    for attempt in 1..=DOWNLOAD_MAX_ATTEMPTS {
+        match aoc_cli::download_capturing(day) {
+            Ok(_) => {
+                read::handle(day, None)?;
+                return Ok(day);
+            }
+            Err(e) if aoc_cli::is_puzzle_locked(&e) => {
+                eprintln!(
+                    "Input for day {day} is not available yet (attempt {attempt}/{DOWNLOAD_MAX_ATTEMPTS}), retrying in {}s...",
+                    DOWNLOAD_RETRY_INTERVAL.as_secs()
+                );
+                thread::sleep(DOWNLOAD_RETRY_INTERVAL);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Err(CommandError::Unavailable(format!(
+        "failed to download input for day {day}. Try again with `cargo download {day}`."
+    )))
+}
+
+fn countdown(day: Day, unlock: DateTime<FixedOffset>) {
+    let mut stdout = stdout();
+
+    loop {
+        let remaining = unlock.signed_duration_since(Utc::now());
+        let Ok(remaining) = remaining.to_std() else {
+            break;
+        };
+        if remaining.is_zero() {
+            break;
+        }
+
+        let secs = remaining.as_secs();
+        print!(
+            "\r⏳ Day {day} unlocks in {ANSI_BOLD}{}d {:02}:{:02}:{:02}{ANSI_RESET} ",
+            secs / 86_400,
+            secs / 3600 % 24,
+            secs / 60 % 60,
+            secs % 60
+        );
+        let _ = stdout.flush();
+
+        // sleep until the next full second so the display does not drift.
+        let subsec = Duration::from_nanos(u64::from(remaining.subsec_nanos()));
+        thread::sleep(if subsec.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            subsec
+        });
+    }
+
+    println!("\r🎄 Day {day} is unlocked!                    ");
+}
@@ -0,0 +1,476 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, Day, all_days,
+    commands::{CommandError, CommandResult},
+    config,
+    run_multi::get_path_for_bin,
+    runner::{self, STACK_SIZE_HINT},
+};
+
+/// Column name of the default input `data/inputs/NN.txt`.
+const DEFAULT_INPUT: &str = "default";
+
+/// An input of a day with the answers it is known to have.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct NamedInput {
+    name: String,
+    path: PathBuf,
+    answers: [Option<String>; 2],
+}
+
+/// The result of checking one part against one input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Check {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// There is no known answer to compare against.
+    Unknown,
+    /// The solution did not run to completion.
+    Crash(String),
+}
+
+impl Check {
+    fn new(expected: Option<&String>, actual: Option<&String>) -> Self {
+        match expected {
+            None => Check::Unknown,
+            Some(expected) if actual == Some(expected) => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.clone(),
+                actual: actual.cloned(),
+            },
+        }
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Check::Pass => '✔',
+            Check::Fail { .. } => '✖',
+            Check::Unknown => '?',
+            Check::Crash(_) => '!',
+        }
+    }
+
+    fn is_failure(&self) -> bool {
+        matches!(self, Check::Fail { .. } | Check::Crash(_))
+    }
+}
+
+/// Checks of both parts of a day against each of its inputs.
+type Row = (Day, Vec<(String, [Check; 2])>);
+
+/// Runs solutions against their inputs and compares the results with the known answers.
+/// Without `all_inputs`, only the default input of each day is checked. Fails if any check fails.
+pub fn handle(day: Option<Day>, all_inputs: bool, is_release: bool) -> CommandResult {
+    let days: Vec<Day> = match day {
+        Some(day) => vec![day],
+        None => all_days()
+            .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+            .collect(),
+    };
+
+    let mut rows: Vec<Row> = vec![];
+
+    for day in days {
+        let inputs = find_inputs(day, all_inputs)?;
+        if inputs.is_empty() {
+            continue;
+        }
+
+        let mut checks = vec![];
+        for input in inputs {
+            eprintln!("Running day {day} with input \"{}\"...", input.name);
+            let parts = match run_solution(day, &input.path, is_release)? {
+                Ok(answers) => {
+                    [0, 1].map(|i| Check::new(input.answers[i].as_ref(), answers[i].as_ref()))
+                }
+                Err(message) => [Check::Crash(message.clone()), Check::Crash(message)],
+            };
+            checks.push((input.name, parts));
+        }
+        rows.push((day, checks));
+    }
+
+    if rows.is_empty() {
+        return Err(CommandError::Unavailable(
+            "no inputs found to verify.".into(),
+        ));
+    }
+
+    println!();
+    print!("{}", render_matrix(&rows));
+    println!("\n✔ correct  ✖ wrong  ? no known answer  ! did not run");
+
+    let failures = render_failures(&rows);
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    println!("\n{ANSI_BOLD}Failures{ANSI_RESET}");
+    print!("{failures}");
+
+    let count = rows
+        .iter()
+        .flat_map(|(_, checks)| checks)
+        .flat_map(|(_, parts)| parts)
+        .filter(|check| check.is_failure())
+        .count();
+
+    Err(CommandError::Unavailable(format!(
+        "{count} check(s) failed."
+    )))
+}
+
+/// Finds the default input of a day and, if `all_inputs` is set, the named inputs in `data/inputs/NN/`.
+/// Named inputs are `.txt` files, their answers are read from a `.answers` file with the same name.
+fn find_inputs(day: Day, all_inputs: bool) -> CommandResult<Vec<NamedInput>> {
+    let dir = &config::get().paths.inputs;
+    let mut inputs = vec![];
+
+    let path = dir.join(format!("{day}.txt"));
+    if exists(&path) {
+        inputs.push(NamedInput {
+            name: DEFAULT_INPUT.into(),
+            answers: read_answers(&path)?,
+            path,
+        });
+    }
+
+    let day_dir = dir.join(day.to_string());
+    if !all_inputs || !day_dir.is_dir() {
+        return Ok(inputs);
+    }
+
+    let entries = fs::read_dir(&day_dir).map_err(|e| CommandError::IO(day_dir.clone(), e))?;
+    // inputs might only exist encrypted, so collect their names from both kinds of files.
+    let names: BTreeSet<String> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let name = name.strip_suffix(".enc").unwrap_or(&name);
+            Some(name.strip_suffix(".txt")?.to_string())
+        })
+        .collect();
+
+    for name in names {
+        let path = day_dir.join(format!("{name}.txt"));
+        inputs.push(NamedInput {
+            answers: read_answers(&path)?,
+            name,
+            path,
+        });
+    }
+
+    Ok(inputs)
+}
+
+/// Whether an input exists, either as plaintext or encrypted.
+fn exists(path: &Path) -> bool {
+    path.exists() || path.with_extension("txt.enc").exists()
+}
+
+fn read_answers(input: &Path) -> CommandResult<[Option<String>; 2]> {
+    let path = input.with_extension("answers");
+    match fs::read_to_string(&path) {
+        Ok(s) => parse_answers(&s)
+            .map_err(|message| CommandError::Parse(format!("\"{}\": {message}", path.display()))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok([None, None]),
+        Err(e) => Err(CommandError::IO(path, e)),
+    }
+}
+
+/// Parses an answers file with one `<part>: <answer>` line per known answer. Lines starting with `#` are ignored.
+fn parse_answers(s: &str) -> Result<[Option<String>; 2], String> {
+    let mut answers = [None, None];
+
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let answer = line
+            .split_once(':')
+            .and_then(|(part, answer)| match part.trim() {
+                "1" => Some((0, answer.trim())),
+                "2" => Some((1, answer.trim())),
+                _ => None,
+            })
+            .filter(|(_, answer)| !answer.is_empty());
+
+        let Some((part, answer)) = answer else {
+            return Err(format!(
+                "line {}: expected `1: <answer>` or `2: <answer>`.",
+                i + 1
+            ));
+        };
+        answers[part] = Some(answer.to_string());
+    }
+
+    Ok(answers)
+}
+
+/// Runs a solution with the given input. Returns the answers of both parts, or a message if the solution failed.
+fn run_solution(
+    day: Day,
+    input: &Path,
+    is_release: bool,
+) -> CommandResult<Result<[Option<String>; 2], String>> {
+    let day_padded = day.to_string();
+    let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    if is_release {
+        args.push("--release");
+    }
+
+    let command = format!("cargo {}", args.join(" "));
+
+    let output = Command::new("cargo")
+        .args(&args)
+        .arg("--")
+        .arg("--json")
+        .arg("--input")
+        .arg(input)
+        .output()
+        .map_err(|e| CommandError::Spawn(command, e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Ok(Err(
+            failure_message(&stderr).unwrap_or_else(|| output.status.to_string())
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .rev()
+        .find_map(parse_answers_json)
+        .ok_or_else(|| "could not parse the output of the solution.".into()))
+}
+
+/// Extracts the reason a solution failed from its stderr: the panic message or the last error.
+fn failure_message(stderr: &str) -> Option<String> {
+    let lines: Vec<&str> = stderr.lines().collect();
+
+    if let Some(line) = lines.iter().find(|line| runner::is_stack_overflow(line)) {
+        return Some(format!("{line}. {STACK_SIZE_HINT}"));
+    }
+
+    // panics are reported as "thread 'main' panicked at <location>:", followed by the message.
+    if let Some(i) = lines.iter().position(|line| line.contains(" panicked at ")) {
+        let (_, location) = lines[i].split_once(" panicked at ")?;
+        let location = location.trim_end_matches(':');
+        return Some(match lines.get(i + 1) {
+            Some(message) => format!("panicked at {location}: {message}"),
+            None => format!("panicked at {location}"),
+        });
+    }
+
+    lines
+        .iter()
+        .rfind(|line| line.starts_with("Error"))
+        .or_else(|| lines.last())
+        .map(|line| (*line).to_string())
+}
+
+/// Parses the answers printed by a solution with `--json`.
+fn parse_answers_json(line: &str) -> Option<[Option<String>; 2]> {
+    let json = JsonValue::from_str(line).ok()?;
+    let parts = json
+        .get::<HashMap<String, JsonValue>>()?
+        .get("parts")?
+        .get::<Vec<JsonValue>>()?;
+
+    let mut answers = [None, None];
+    for part in parts {
+        let part = part.get::<HashMap<String, JsonValue>>()?;
+        let index = match part.get("part")?.get::<f64>()? {
+            1.0 => 0,
+            2.0 => 1,
+            _ => return None,
+        };
+        answers[index] = part.get("answer")?.get::<String>().cloned();
+    }
+
+    Some(answers)
+}
+
+/// Renders a table with one row per day and one column per input name.
+fn render_matrix(rows: &[Row]) -> String {
+    let mut columns: Vec<&str> = vec![];
+    for (_, checks) in rows {
+        for (name, _) in checks {
+            if !columns.contains(&name.as_str()) {
+                columns.push(name);
+            }
+        }
+    }
+    // keep the default input first, the remaining inputs are sorted by name.
+    columns.sort_unstable_by_key(|name| (*name != DEFAULT_INPUT, *name));
+
+    let widths: Vec<usize> = columns.iter().map(|name| name.len().max(3)).collect();
+
+    let mut s = format!("{ANSI_BOLD}Day{ANSI_RESET}");
+    for (name, width) in columns.iter().zip(&widths) {
+        let _ = write!(s, " | {ANSI_BOLD}{name:width$}{ANSI_RESET}");
+    }
+    s.push('\n');
+
+    for (day, checks) in rows {
+        let _ = write!(s, "{:<3}", day.to_string());
+        for (name, width) in columns.iter().zip(&widths) {
+            let cell = checks.iter().find(|(input, _)| input == name).map_or_else(
+                || "-".into(),
+                |(_, [one, two])| format!("{}{}", one.symbol(), two.symbol()),
+            );
+            let _ = write!(s, " | {cell:width$}");
+        }
+        s.push('\n');
+    }
+
+    s
+}
+
+/// Describes each failed check on its own line.
+fn render_failures(rows: &[Row]) -> String {
+    let mut s = String::new();
+
+    for (day, checks) in rows {
+        for (name, parts) in checks {
+            if let Check::Crash(message) = &parts[0] {
+                let _ = writeln!(s, "Day {day}, input \"{name}\": {message}");
+                continue;
+            }
+
+            for (part, check) in (1..).zip(parts) {
+                if let Check::Fail { expected, actual } = check {
+                    let actual = actual.as_deref().unwrap_or("no answer");
+                    let _ = writeln!(
+                        s,
+                        "Day {day}, input \"{name}\", part {part}: expected {expected}, got {actual}"
+                    );
+                }
+            }
+        }
+    }
+
+    s
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{
+        Check, failure_message, parse_answers, parse_answers_json, render_failures, render_matrix,
+    };
+    use crate::{
+        day,
+        template::{ANSI_BOLD, ANSI_RESET},
+    };
+
+    #[test]
+    fn parses_answers() {
+        assert_eq!(
+            parse_answers("# bob's input\n1: 42\n\n2:  abc \n"),
+            Ok([Some("42".into()), Some("abc".into())])
+        );
+        assert_eq!(parse_answers("2: 7"), Ok([None, Some("7".into())]));
+        assert!(parse_answers("3: 7").is_err());
+        assert!(parse_answers("1:").is_err());
+    }
+
+    #[test]
+    fn parses_answers_from_json_output() {
+        let json = r#"{"day":9,"parts":[{"part":1,"answer":"42","nanos":1,"samples":1},{"part":2,"answer":null,"nanos":1,"samples":1}]}"#;
+        assert_eq!(parse_answers_json(json), Some([Some("42".into()), None]));
+        assert_eq!(parse_answers_json("Part 1: 42"), None);
+    }
+
+    #[test]
+    fn extracts_failure_messages() {
+        assert_eq!(
+            failure_message(
+                "thread 'main' (1) panicked at src/bin/03.rs:37:41:\nindex out of bounds\nnote: run with `RUST_BACKTRACE=1`\n"
+            )
+            .as_deref(),
+            Some("panicked at src/bin/03.rs:37:41: index out of bounds")
+        );
+        assert_eq!(
+            failure_message("Error: input file is empty.\n").as_deref(),
+            Some("Error: input file is empty.")
+        );
+        assert!(
+            failure_message(
+                "\nthread 'part 1' (7) has overflowed its stack\nfatal runtime error: stack overflow, aborting\n"
+            )
+            .is_some_and(|x| x.starts_with("thread 'part 1' (7) has overflowed its stack. "))
+        );
+        assert_eq!(failure_message(""), None);
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answer = Some("1".to_string());
+        assert_eq!(Check::new(answer.as_ref(), answer.as_ref()), Check::Pass);
+        assert_eq!(Check::new(None, answer.as_ref()), Check::Unknown);
+        assert_eq!(
+            Check::new(answer.as_ref(), None),
+            Check::Fail {
+                expected: "1".into(),
+                actual: None
+            }
+        );
+    }
+
+    #[test]
+    fn renders_matrix_and_failures() {
+        let fail = Check::Fail {
+            expected: "2".into(),
+            actual: Some("3".into()),
+        };
+        let rows = vec![
+            (
+                day!(9),
+                vec![
+                    ("default".into(), [Check::Pass, Check::Pass]),
+                    ("carol".into(), [Check::Pass, fail]),
+                    ("alice".into(), [Check::Unknown, Check::Unknown]),
+                ],
+            ),
+            (
+                day!(11),
+                vec![(
+                    "default".into(),
+                    [Check::Crash("oops".into()), Check::Crash("oops".into())],
+                )],
+            ),
+        ];
+
+        let b = ANSI_BOLD;
+        let r = ANSI_RESET;
+        assert_eq!(
+            render_matrix(&rows),
+            format!(
+                "{b}Day{r} | {b}default{r} | {b}alice{r} | {b}carol{r}\n\
+                09  | ✔✔      | ??    | ✔✖   \n\
+                11  | !!      | -     | -    \n"
+            )
+        );
+        assert_eq!(
+            render_failures(&rows),
+            "Day 09, input \"carol\", part 2: expected 2, got 3\nDay 11, input \"default\": oops\n"
+        );
+    }
+}
@@ -0,0 +1,521 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+/// Every setting is optional, missing settings fall back to the template defaults.
+use std::{
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::template::{Day, cli::suggest};
+
+pub const CONFIG_PATH: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// The event year passed to aoc-cli. The `AOC_YEAR` environment variable takes precedence.
+    pub year: Option<u16>,
+    /// How many puzzles the event has, e.g. 12 from 2025 onwards.
+    pub days: u8,
+    /// Template used by `scaffold` when `--template` is not passed.
+    pub default_template: String,
+    /// Path or name of the aoc-cli binary.
+    pub aoc_cli: String,
+    /// Readme that benchmarks are written to.
+    pub readme: PathBuf,
+    pub paths: Paths,
+    pub bench: Bench,
+    pub stack: Stack,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Paths {
+    /// Root folder for data files, `read_file` looks up folders other than `inputs` and `examples` in here.
+    pub data: PathBuf,
+    pub inputs: PathBuf,
+    pub examples: PathBuf,
+    pub puzzles: PathBuf,
+    pub templates: PathBuf,
+    pub timings: PathBuf,
+    /// Key for encrypted inputs. Keep it outside of the repository, a leading `~` refers to the home directory.
+    pub key_file: PathBuf,
+}
+
+/// Settings for benchmarks run with `--time`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bench {
+    /// Approximate time each part is benchmarked for.
+    pub budget_ms: u64,
+    pub min_samples: u64,
+    pub max_samples: u64,
+}
+
+/// Stack sizes of the thread that parts run on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stack {
+    pub size_mb: u64,
+    /// Stack sizes of days that differ from `size_mb`, e.g. because they recurse deeply.
+    pub days: Vec<(Day, u64)>,
+}
+
+impl Stack {
+    /// Returns the stack size of `day` in MiB.
+    pub fn size_mb_for(&self, day: Day) -> u64 {
+        self.days
+            .iter()
+            .find(|(x, _)| *x == day)
+            .map_or(self.size_mb, |(_, size_mb)| *size_mb)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            days: 25,
+            default_template: "default".into(),
+            aoc_cli: "aoc".into(),
+            readme: "README.md".into(),
+            paths: Paths {
+                data: "data".into(),
+                inputs: "data/inputs".into(),
+                examples: "data/examples".into(),
+                puzzles: "data/puzzles".into(),
+                templates: "data/templates".into(),
+                timings: "data/timings.json".into(),
+                key_file: "~/.config/advent_of_code/inputs.key".into(),
+            },
+            bench: Bench {
+                budget_ms: 1000,
+                min_samples: 10,
+                max_samples: 10000,
+            },
+            stack: Stack {
+                size_mb: 8,
+                days: vec![],
+            },
+        }
+    }
+}
+
+/// An error which can be returned when loading the configuration.
+#[derive(Debug)]
+pub enum ConfigError {
+    IO(PathBuf, io::Error),
+    /// The file is not valid TOML, or uses TOML features that are not supported.
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    /// A key is unknown or has an invalid value.
+    Invalid {
+        line: usize,
+        key: String,
+        message: String,
+    },
+}
+
+impl Error for ConfigError {}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::IO(path, e) => write!(f, "could not read \"{}\": {e}", path.display()),
+            ConfigError::Syntax { line, message } => write!(f, "{CONFIG_PATH}:{line}: {message}"),
+            ConfigError::Invalid { line, key, message } => {
+                write!(f, "{CONFIG_PATH}:{line}: invalid key \"{key}\": {message}")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+}
+
+#[derive(Debug)]
+struct Entry {
+    key: String,
+    value: Value,
+    line: usize,
+}
+
+/// Removes a trailing comment that is not part of a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+fn parse_string(s: &str) -> Option<String> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return None,
+            '\\' => value.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                c @ ('\\' | '"') => c,
+                _ => return None,
+            }),
+            c => value.push(c),
+        }
+    }
+
+    Some(value)
+}
+
+fn parse_value(s: &str) -> Option<Value> {
+    match s {
+        "true" => Some(Value::Boolean(true)),
+        "false" => Some(Value::Boolean(false)),
+        s if s.starts_with('"') => parse_string(s).map(Value::String),
+        s => s.replace('_', "").parse().ok().map(Value::Integer),
+    }
+}
+
+fn is_bare_key(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Parses the subset of TOML used by `aoc.toml`: tables, bare keys, basic strings, integers and booleans.
+fn parse_entries(s: &str) -> Result<Vec<Entry>, ConfigError> {
+    let mut entries: Vec<Entry> = vec![];
+    let mut table = String::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let line_number = i + 1;
+        let syntax_error = |message: &str| ConfigError::Syntax {
+            line: line_number,
+            message: message.into(),
+        };
+
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .ok_or_else(|| syntax_error("expected `]` after table name"))?
+                .trim();
+            if !is_bare_key(name) {
+                return Err(syntax_error("expected a table name like `[bench]`"));
+            }
+            table = format!("{name}.");
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| syntax_error("expected `key = value`"))?;
+        let key = key.trim();
+        if !is_bare_key(key) {
+            return Err(syntax_error(
+                "expected a key of letters, digits, `_` or `-`",
+            ));
+        }
+
+        let key = format!("{table}{key}");
+        let value = parse_value(value.trim()).ok_or_else(|| ConfigError::Invalid {
+            line: line_number,
+            key: key.clone(),
+            message: "expected a string, integer or boolean".into(),
+        })?;
+
+        if let Some(previous) = entries.iter().find(|entry| entry.key == key) {
+            return Err(ConfigError::Invalid {
+                line: line_number,
+                key,
+                message: format!("already set on line {}", previous.line),
+            });
+        }
+
+        entries.push(Entry {
+            key,
+            value,
+            line: line_number,
+        });
+    }
+
+    Ok(entries)
+}
+
+/* -------------------------------------------------------------------------- */
+
+const KEYS: &[&str] = &[
+    "year",
+    "days",
+    "default_template",
+    "aoc_cli",
+    "readme",
+    "paths.data",
+    "paths.inputs",
+    "paths.examples",
+    "paths.puzzles",
+    "paths.templates",
+    "paths.timings",
+    "paths.key_file",
+    "bench.budget_ms",
+    "bench.min_samples",
+    "bench.max_samples",
+    "stack.size_mb",
+];
+
+/// Stack sizes are limited to 64 GiB, which is far more than any solution should need.
+const MAX_STACK_MB: i64 = 65536;
+
+impl Entry {
+    fn invalid(&self, message: impl Into<String>) -> ConfigError {
+        ConfigError::Invalid {
+            line: self.line,
+            key: self.key.clone(),
+            message: message.into(),
+        }
+    }
+
+    fn string(&self) -> Result<String, ConfigError> {
+        match &self.value {
+            Value::String(s) if !s.is_empty() => Ok(s.clone()),
+            _ => Err(self.invalid("expected a non-empty string")),
+        }
+    }
+
+    fn integer<T: TryFrom<i64>>(&self, min: i64, max: i64) -> Result<T, ConfigError> {
+        match self.value {
+            Value::Integer(x) if (min..=max).contains(&x) => {
+                T::try_from(x).map_err(|_| self.invalid("integer out of range"))
+            }
+            _ => Err(self.invalid(format!("expected an integer between {min} and {max}"))),
+        }
+    }
+}
+
+impl Config {
+    /// Parses the contents of an `aoc.toml` file.
+    pub fn parse(s: &str) -> Result<Self, ConfigError> {
+        let mut config = Config::default();
+        // lines of `bench.min_samples` and `bench.max_samples`, to point at them if they conflict.
+        let mut sample_lines = (None, None);
+        // `day_7` and `day_07` are different keys for the same day.
+        let mut stack_lines: Vec<(Day, usize)> = vec![];
+
+        for entry in parse_entries(s)? {
+            match entry.key.as_str() {
+                "year" => config.year = Some(entry.integer(2015, 9999)?),
+                "days" => config.days = entry.integer(1, 25)?,
+                "default_template" => config.default_template = entry.string()?,
+                "aoc_cli" => config.aoc_cli = entry.string()?,
+                "readme" => config.readme = entry.string()?.into(),
+                "paths.data" => config.paths.data = entry.string()?.into(),
+                "paths.inputs" => config.paths.inputs = entry.string()?.into(),
+                "paths.examples" => config.paths.examples = entry.string()?.into(),
+                "paths.puzzles" => config.paths.puzzles = entry.string()?.into(),
+                "paths.templates" => config.paths.templates = entry.string()?.into(),
+                "paths.timings" => config.paths.timings = entry.string()?.into(),
+                "paths.key_file" => config.paths.key_file = entry.string()?.into(),
+                "bench.budget_ms" => config.bench.budget_ms = entry.integer(1, i64::MAX)?,
+                "bench.min_samples" => {
+                    config.bench.min_samples = entry.integer(1, i64::MAX)?;
+                    sample_lines.0 = Some(entry.line);
+                }
+                "bench.max_samples" => {
+                    config.bench.max_samples = entry.integer(1, i64::MAX)?;
+                    sample_lines.1 = Some(entry.line);
+                }
+                "stack.size_mb" => config.stack.size_mb = entry.integer(1, MAX_STACK_MB)?,
+                key if key.starts_with("stack.day_") => {
+                    let day = key["stack.day_".len()..]
+                        .parse::<Day>()
+                        .map_err(|e| entry.invalid(format!("{e} after `day_`")))?;
+                    if let Some((_, line)) = stack_lines.iter().find(|(other, _)| *other == day) {
+                        return Err(entry.invalid(format!("already set on line {line}")));
+                    }
+                    stack_lines.push((day, entry.line));
+                    config
+                        .stack
+                        .days
+                        .push((day, entry.integer(1, MAX_STACK_MB)?));
+                }
+                key => {
+                    let message = match suggest(key, KEYS.iter().copied()) {
+                        Some(suggestion) => format!("unknown key, did you mean \"{suggestion}\"?"),
+                        None => "unknown key".into(),
+                    };
+                    return Err(entry.invalid(message));
+                }
+            }
+        }
+
+        if config.bench.min_samples > config.bench.max_samples {
+            let (key, line) = match sample_lines {
+                (Some(line), _) => ("bench.min_samples", line),
+                (None, line) => ("bench.max_samples", line.unwrap_or_default()),
+            };
+            return Err(ConfigError::Invalid {
+                line,
+                key: key.into(),
+                message: format!(
+                    "`bench.min_samples` ({}) must not be greater than `bench.max_samples` ({})",
+                    config.bench.min_samples, config.bench.max_samples
+                ),
+            });
+        }
+
+        Ok(config)
+    }
+
+    /// Reads the configuration from `path`. A missing file yields the default configuration.
+    pub fn read(path: &Path) -> Result<Self, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(ConfigError::IO(path.to_path_buf(), e)),
+        }
+    }
+}
+
+/// Loads `aoc.toml` from the working directory. Call this once at startup to report configuration errors
+/// before [`get`] is used.
+pub fn init() -> Result<&'static Config, ConfigError> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+    let config = Config::read(Path::new(CONFIG_PATH))?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// Returns the project configuration, loading it if necessary.
+///
+/// # Panics
+/// Panics if `aoc.toml` is invalid and [`init`] was not called to handle the error.
+pub fn get() -> &'static Config {
+    init().unwrap_or_else(|e| panic!("{e}"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Config, ConfigError};
+    use crate::template::Day;
+
+    #[test]
+    fn uses_defaults_for_empty_files() {
+        assert_eq!(
+            Config::parse("# nothing here\n").unwrap(),
+            Config::default()
+        );
+    }
+
+    #[test]
+    fn parses_all_value_types() {
+        let config = Config::parse(
+            r#"
+year = 2025 # comment
+days = 12
+aoc_cli = "/opt/aoc # not a comment"
+
+[paths]
+inputs = "secret/inputs"
+
+[bench]
+budget_ms = 1_500
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2025));
+        assert_eq!(config.days, 12);
+        assert_eq!(config.aoc_cli, "/opt/aoc # not a comment");
+        assert_eq!(config.paths.inputs.to_str(), Some("secret/inputs"));
+        assert_eq!(config.paths.examples.to_str(), Some("data/examples"));
+        assert_eq!(config.bench.budget_ms, 1500);
+    }
+
+    #[test]
+    fn parses_stack_sizes() {
+        let config = Config::parse("[stack]\nsize_mb = 16\nday_07 = 256").unwrap();
+        assert_eq!(config.stack.size_mb_for(Day::new(7).unwrap()), 256);
+        assert_eq!(config.stack.size_mb_for(Day::new(8).unwrap()), 16);
+
+        assert!(matches!(
+            Config::parse("[stack]\nday_26 = 64"),
+            Err(ConfigError::Invalid { line: 2, .. })
+        ));
+        assert!(Config::parse("[stack]\nsize_mb = 0").is_err());
+        assert!(matches!(
+            Config::parse("[stack]\nday_7 = 64\nday_07 = 128"),
+            Err(ConfigError::Invalid { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn points_at_invalid_values() {
+        let err = Config::parse("year = 2025\n\n[bench]\nmin_samples = \"ten\"").unwrap_err();
+        assert!(matches!(
+            &err,
+            ConfigError::Invalid { line: 4, key, .. } if key == "bench.min_samples"
+        ));
+        assert!(
+            err.to_string()
+                .starts_with("aoc.toml:4: invalid key \"bench.min_samples\"")
+        );
+
+        assert!(matches!(
+            Config::parse("days = 26"),
+            Err(ConfigError::Invalid { line: 1, .. })
+        ));
+        assert!(matches!(
+            Config::parse("[bench]\nmin_samples = 100\nmax_samples = 10"),
+            Err(ConfigError::Invalid { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn suggests_unknown_keys() {
+        let err = Config::parse("[paths]\ninptus = \"x\"").unwrap_err();
+        assert!(err.to_string().contains("did you mean \"paths.inputs\"?"));
+    }
+
+    #[test]
+    fn errors_for_syntax_errors() {
+        assert!(matches!(
+            Config::parse("year = 2025\n[bench"),
+            Err(ConfigError::Syntax { line: 2, .. })
+        ));
+        assert!(matches!(
+            Config::parse("year"),
+            Err(ConfigError::Syntax { line: 1, .. })
+        ));
+        assert!(matches!(
+            Config::parse("aoc_cli = \"unterminated"),
+            Err(ConfigError::Invalid { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,425 @@
+/// Encryption of puzzle inputs with ChaCha20-Poly1305 (RFC 8439), so they can be committed without publishing them.
+/// The key is stored in a file outside of the repository, see `paths.key_file` in `aoc.toml`.
+use std::{
+    collections::hash_map::RandomState,
+    env,
+    error::Error,
+    fmt::Display,
+    fs::{self, File, OpenOptions},
+    hash::BuildHasher,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::template::config;
+
+/// Extension appended to the file name of encrypted files, e.g. `01.txt.enc`.
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+/// Identifies the file format, so that corrupt or foreign files are reported as such.
+const MAGIC: &[u8; 8] = b"aoc-enc1";
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// An error which can be returned when encrypting or decrypting inputs.
+#[derive(Debug)]
+pub enum CryptoError {
+    KeyNotFound(PathBuf),
+    InvalidKey(PathBuf),
+    IO(PathBuf, io::Error),
+    /// The data is not an encrypted file.
+    InvalidFormat,
+    /// The data was encrypted with a different key or has been modified.
+    Authentication,
+}
+
+impl Error for CryptoError {}
+
+impl Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptoError::KeyNotFound(path) => write!(
+                f,
+                "no key found at \"{}\". Copy the key from another machine or run `cargo encrypt` to create one.",
+                path.display()
+            ),
+            CryptoError::InvalidKey(path) => write!(
+                f,
+                "key file \"{}\" does not contain 64 hexadecimal characters.",
+                path.display()
+            ),
+            CryptoError::IO(path, e) => write!(f, "could not access \"{}\": {e}", path.display()),
+            CryptoError::InvalidFormat => write!(f, "not an encrypted input file."),
+            CryptoError::Authentication => write!(
+                f,
+                "decryption failed, the file was encrypted with a different key or has been modified."
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn quarter_round(s: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    s[a] = s[a].wrapping_add(s[b]);
+    s[d] = (s[d] ^ s[a]).rotate_left(16);
+    s[c] = s[c].wrapping_add(s[d]);
+    s[b] = (s[b] ^ s[c]).rotate_left(12);
+    s[a] = s[a].wrapping_add(s[b]);
+    s[d] = (s[d] ^ s[a]).rotate_left(8);
+    s[c] = s[c].wrapping_add(s[d]);
+    s[b] = (s[b] ^ s[c]).rotate_left(7);
+}
+
+fn le_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn chacha20_block(key: &[u8; 32], counter: u32, nonce: &[u8; NONCE_LEN]) -> [u8; 64] {
+    let mut state = [0u32; 16];
+    state[..4].copy_from_slice(&[0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574]);
+    for i in 0..8 {
+        state[4 + i] = le_u32(&key[i * 4..]);
+    }
+    state[12] = counter;
+    for i in 0..3 {
+        state[13 + i] = le_u32(&nonce[i * 4..]);
+    }
+
+    let mut working = state;
+    for _ in 0..10 {
+        quarter_round(&mut working, 0, 4, 8, 12);
+        quarter_round(&mut working, 1, 5, 9, 13);
+        quarter_round(&mut working, 2, 6, 10, 14);
+        quarter_round(&mut working, 3, 7, 11, 15);
+        quarter_round(&mut working, 0, 5, 10, 15);
+        quarter_round(&mut working, 1, 6, 11, 12);
+        quarter_round(&mut working, 2, 7, 8, 13);
+        quarter_round(&mut working, 3, 4, 9, 14);
+    }
+
+    let mut block = [0u8; 64];
+    for (i, (word, initial)) in working.iter().zip(state).enumerate() {
+        block[i * 4..i * 4 + 4].copy_from_slice(&word.wrapping_add(initial).to_le_bytes());
+    }
+    block
+}
+
+/// XORs `data` with the ChaCha20 key stream, starting at block `counter`.
+fn chacha20_xor(key: &[u8; 32], counter: u32, nonce: &[u8; NONCE_LEN], data: &mut [u8]) {
+    for (i, chunk) in data.chunks_mut(64).enumerate() {
+        #[allow(clippy::cast_possible_truncation)]
+        let block = chacha20_block(key, counter.wrapping_add(i as u32), nonce);
+        for (byte, key_byte) in chunk.iter_mut().zip(block) {
+            *byte ^= key_byte;
+        }
+    }
+}
+
+/// Computes the Poly1305 tag of `message`, using 26-bit limbs.
+fn poly1305(key: &[u8; 32], message: &[u8]) -> [u8; TAG_LEN] {
+    const MASK: u32 = 0x3ff_ffff;
+
+    let r = [
+        le_u32(&key[0..]) & 0x3ff_ffff,
+        (le_u32(&key[3..]) >> 2) & 0x3ff_ff03,
+        (le_u32(&key[6..]) >> 4) & 0x3ff_c0ff,
+        (le_u32(&key[9..]) >> 6) & 0x3f0_3fff,
+        (le_u32(&key[12..]) >> 8) & 0x00f_ffff,
+    ]
+    .map(u64::from);
+    let s = [r[1] * 5, r[2] * 5, r[3] * 5, r[4] * 5];
+    let mut h = [0u32; 5];
+
+    for chunk in message.chunks(16) {
+        let mut block = [0u8; 17];
+        block[..chunk.len()].copy_from_slice(chunk);
+        block[chunk.len()] = 1;
+
+        h[0] += le_u32(&block[0..]) & MASK;
+        h[1] += (le_u32(&block[3..]) >> 2) & MASK;
+        h[2] += (le_u32(&block[6..]) >> 4) & MASK;
+        h[3] += (le_u32(&block[9..]) >> 6) & MASK;
+        h[4] += (le_u32(&block[12..]) >> 8) | (u32::from(block[16]) << 24);
+
+        let h64 = h.map(u64::from);
+        let d = [
+            h64[0] * r[0] + h64[1] * s[3] + h64[2] * s[2] + h64[3] * s[1] + h64[4] * s[0],
+            h64[0] * r[1] + h64[1] * r[0] + h64[2] * s[3] + h64[3] * s[2] + h64[4] * s[1],
+            h64[0] * r[2] + h64[1] * r[1] + h64[2] * r[0] + h64[3] * s[3] + h64[4] * s[2],
+            h64[0] * r[3] + h64[1] * r[2] + h64[2] * r[1] + h64[3] * r[0] + h64[4] * s[3],
+            h64[0] * r[4] + h64[1] * r[3] + h64[2] * r[2] + h64[3] * r[1] + h64[4] * r[0],
+        ];
+
+        let mut carry = 0u64;
+        for i in 0..5 {
+            let x = d[i] + carry;
+            #[allow(clippy::cast_possible_truncation)]
+            {
+                h[i] = (x as u32) & MASK;
+            }
+            carry = x >> 26;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            h[0] += (carry as u32) * 5;
+        }
+        h[1] += h[0] >> 26;
+        h[0] &= MASK;
+    }
+
+    // fully carry h.
+    for i in 1..5 {
+        h[i] += h[i - 1] >> 26;
+        h[i - 1] &= MASK;
+    }
+    h[0] += (h[4] >> 26) * 5;
+    h[4] &= MASK;
+    h[1] += h[0] >> 26;
+    h[0] &= MASK;
+
+    // h + 5 overflows 2^130 exactly if h >= p = 2^130 - 5, in which case h - p = h + 5 - 2^130.
+    let mut g = [0u32; 5];
+    let mut carry = 5;
+    for i in 0..5 {
+        g[i] = h[i] + carry;
+        carry = g[i] >> 26;
+        g[i] &= MASK;
+    }
+    if carry == 1 {
+        h = g;
+    }
+
+    let h0 = h[0] | (h[1] << 26);
+    let h1 = (h[1] >> 6) | (h[2] << 20);
+    let h2 = (h[2] >> 12) | (h[3] << 14);
+    let h3 = (h[3] >> 18) | (h[4] << 8);
+
+    let mut tag = [0u8; TAG_LEN];
+    let mut f = 0u64;
+    for (i, word) in [h0, h1, h2, h3].into_iter().enumerate() {
+        f = u64::from(word) + u64::from(le_u32(&key[16 + i * 4..])) + (f >> 32);
+        #[allow(clippy::cast_possible_truncation)]
+        tag[i * 4..i * 4 + 4].copy_from_slice(&(f as u32).to_le_bytes());
+    }
+    tag
+}
+
+fn aead_tag(key: &[u8; 32], nonce: &[u8; NONCE_LEN], ciphertext: &[u8]) -> [u8; TAG_LEN] {
+    let block = chacha20_block(key, 0, nonce);
+    let mut poly_key = [0u8; 32];
+    poly_key.copy_from_slice(&block[..32]);
+
+    // no additional data is authenticated, so the mac data is the padded ciphertext followed by both lengths.
+    let mut mac_data = ciphertext.to_vec();
+    mac_data.resize(ciphertext.len().next_multiple_of(16), 0);
+    mac_data.extend(0u64.to_le_bytes());
+    mac_data.extend((ciphertext.len() as u64).to_le_bytes());
+
+    poly1305(&poly_key, &mac_data)
+}
+
+/// Compares two tags in constant time.
+fn tags_match(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Fills a buffer with random bytes from the operating system.
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+
+    let from_os = File::open("/dev/urandom").and_then(|mut file| file.read_exact(&mut bytes));
+    if from_os.is_err() {
+        // on platforms without `/dev/urandom`, fall back to the randomly seeded keys of std's hasher.
+        let state = RandomState::new();
+        for (i, chunk) in bytes.chunks_mut(8).enumerate() {
+            let value = state.hash_one(i).to_le_bytes();
+            chunk.copy_from_slice(&value[..chunk.len()]);
+        }
+    }
+
+    bytes
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A 256-bit key, stored as hexadecimal text.
+pub struct Key([u8; 32]);
+
+impl Key {
+    pub fn generate() -> Self {
+        Self(random_bytes())
+    }
+
+    /// Returns the location of the key file, with a leading `~` expanded to the home directory.
+    pub fn path() -> PathBuf {
+        let path = &config::get().paths.key_file;
+        match (path.strip_prefix("~"), env::var_os("HOME")) {
+            (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+            _ => path.clone(),
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Self, CryptoError> {
+        let hex = fs::read_to_string(path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => CryptoError::KeyNotFound(path.to_path_buf()),
+            _ => CryptoError::IO(path.to_path_buf(), e),
+        })?;
+        let hex = hex.trim();
+
+        let mut key = [0u8; 32];
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(CryptoError::InvalidKey(path.to_path_buf()));
+        }
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+                .map_err(|_| CryptoError::InvalidKey(path.to_path_buf()))?;
+        }
+
+        Ok(Self(key))
+    }
+
+    /// Writes the key to a new file that is only readable by the current user. Existing keys are never overwritten.
+    pub fn write(&self, path: &Path) -> Result<(), CryptoError> {
+        let io_error = |e| CryptoError::IO(path.to_path_buf(), e);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let hex: String = self.0.iter().map(|byte| format!("{byte:02x}")).collect();
+        let mut file = options.open(path).map_err(io_error)?;
+        writeln!(file, "{hex}").map_err(io_error)
+    }
+
+    /// Encrypts `plaintext` with a random nonce.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        self.encrypt_with_nonce(plaintext, &random_bytes())
+    }
+
+    fn encrypt_with_nonce(&self, plaintext: &[u8], nonce: &[u8; NONCE_LEN]) -> Vec<u8> {
+        let mut ciphertext = plaintext.to_vec();
+        chacha20_xor(&self.0, 1, nonce, &mut ciphertext);
+
+        let mut data = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len() + TAG_LEN);
+        data.extend(MAGIC);
+        data.extend(nonce);
+        data.extend(aead_tag(&self.0, nonce, &ciphertext));
+        data.extend(ciphertext);
+        data
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let rest = data.strip_prefix(MAGIC).ok_or(CryptoError::InvalidFormat)?;
+        if rest.len() < NONCE_LEN + TAG_LEN {
+            return Err(CryptoError::InvalidFormat);
+        }
+
+        let (nonce, rest) = rest.split_at(NONCE_LEN);
+        let (tag, ciphertext) = rest.split_at(TAG_LEN);
+        let nonce: &[u8; NONCE_LEN] = nonce.try_into().map_err(|_| CryptoError::InvalidFormat)?;
+
+        if !tags_match(tag, &aead_tag(&self.0, nonce, ciphertext)) {
+            return Err(CryptoError::Authentication);
+        }
+
+        let mut plaintext = ciphertext.to_vec();
+        chacha20_xor(&self.0, 1, nonce, &mut plaintext);
+        Ok(plaintext)
+    }
+}
+
+/// Returns the path of the encrypted counterpart of `path`, e.g. `01.txt.enc` for `01.txt`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(ENCRYPTED_EXTENSION);
+    path.with_file_name(name)
+}
+
+/// Reads and decrypts the encrypted counterpart of `path` with the configured key.
+pub fn read_encrypted(path: &Path) -> Result<Vec<u8>, CryptoError> {
+    let encrypted_path = encrypted_path(path);
+    let data = fs::read(&encrypted_path).map_err(|e| CryptoError::IO(encrypted_path, e))?;
+    Key::read(&Key::path())?.decrypt(&data)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{CryptoError, Key, chacha20_block, poly1305};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    fn test_key() -> Key {
+        Key(core::array::from_fn(|i| i as u8))
+    }
+
+    #[test]
+    fn chacha20_block_matches_rfc_8439() {
+        let key = core::array::from_fn(|i| i as u8);
+        let nonce = [0, 0, 0, 9, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+        let block = chacha20_block(&key, 1, &nonce);
+        assert_eq!(hex(&block[..16]), "10f1e7e4d13b5915500fdd1fa32071c4");
+    }
+
+    #[test]
+    fn poly1305_matches_rfc_8439() {
+        let key = [
+            0x85, 0xd6, 0xbe, 0x78, 0x57, 0x55, 0x6d, 0x33, 0x7f, 0x44, 0x52, 0xfe, 0x42, 0xd5,
+            0x06, 0xa8, 0x01, 0x03, 0x80, 0x8a, 0xfb, 0x0d, 0xb2, 0xfd, 0x4a, 0xbf, 0xf6, 0xaf,
+            0x41, 0x49, 0xf5, 0x1b,
+        ];
+        let tag = poly1305(&key, b"Cryptographic Forum Research Group");
+        assert_eq!(hex(&tag), "a8061dc1305136c6c22b8baf0c0127a9");
+    }
+
+    #[test]
+    fn encrypts_like_chacha20_poly1305() {
+        let nonce = core::array::from_fn(|i| i as u8);
+        let data = test_key().encrypt_with_nonce(b"1,2,3\n4,5,6\n", &nonce);
+        let (tag, ciphertext) = data[20..].split_at(16);
+        assert_eq!(hex(ciphertext), "b8d73a2c1a1d916c82af09f9");
+        assert_eq!(hex(tag), "40e592bf81a741270588f11bfba00282");
+
+        let data = test_key().encrypt_with_nonce(&(0..200).collect::<Vec<u8>>(), &nonce);
+        assert_eq!(hex(&data[20..36]), "01a2a950a2c2f8af5ebfa84a8b4d66ea");
+        assert_eq!(hex(&data[36..44]), "89fa0a032d12a347");
+        assert_eq!(hex(&data[100..108]), "3939fe8ea1516aae");
+    }
+
+    #[test]
+    fn decrypts_encrypted_data() {
+        let key = test_key();
+        let data = key.encrypt(b"1,2,3\n");
+        assert_eq!(key.decrypt(&data).unwrap(), b"1,2,3\n");
+        assert_ne!(data, key.encrypt(b"1,2,3\n"));
+    }
+
+    #[test]
+    fn rejects_modified_data() {
+        let key = test_key();
+        let mut data = key.encrypt(b"1,2,3\n");
+        *data.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            key.decrypt(&data),
+            Err(CryptoError::Authentication)
+        ));
+        assert!(matches!(
+            key.decrypt(b"1,2,3"),
+            Err(CryptoError::InvalidFormat)
+        ));
+        assert!(matches!(
+            Key::generate().decrypt(&key.encrypt(b"1")),
+            Err(CryptoError::Authentication)
+        ));
+    }
+}
@@ -2,8 +2,10 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::config;
+
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
@@ -39,16 +41,41 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's between the 1st and the last day of december's event, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        if today.month() == 12 && today.day() <= u32::from(config::get().days) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
         }
     }
+
+    /// Returns the next day of advent that unlocks after the current time, together with its unlock time.
+    /// Puzzles unlock at midnight server time.
+    pub fn next_unlock() -> Option<(Self, DateTime<FixedOffset>)> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        Self::next_unlock_after(Utc::now().with_timezone(&offset), config::get().days)
+    }
+
+    fn next_unlock_after(
+        now: DateTime<FixedOffset>,
+        days: u8,
+    ) -> Option<(Self, DateTime<FixedOffset>)> {
+        let (year, day) = match (now.month(), now.day()) {
+            (12, day) if day < u32::from(days) => (now.year(), day + 1),
+            (12, _) => (now.year() + 1, 1),
+            _ => (now.year(), 1),
+        };
+
+        let unlock = now
+            .timezone()
+            .with_ymd_and_hms(year, 12, day, 0, 0, 0)
+            .single()?;
+
+        Some((Self::new(u8::try_from(day).ok()?)?, unlock))
+    }
 }
 
 impl Display for Day {
@@ -94,20 +121,30 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last day configured in `aoc.toml` (the 25th by default).
 pub fn all_days() -> AllDays {
-    AllDays::new()
+    AllDays::up_to(config::get().days)
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields days of advent from the 1st up to a last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
+    /// Yields every day from the 1st to the 25th.
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::up_to(25)
+    }
+
+    /// Yields every day from the 1st to `last`, which is capped at the 25th.
+    pub fn up_to(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(25),
+        }
     }
 }
 
@@ -115,10 +152,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and `last` is capped at 25.
         let day = Day(self.current);
         self.current += 1;
 
@@ -141,9 +178,17 @@ macro_rules! day {
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
-    use super::{Day, all_days};
+    use super::{AllDays, Day, all_days};
+
+    #[test]
+    fn all_days_iterator_up_to() {
+        let days: Vec<_> = AllDays::up_to(12).collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+        assert_eq!(AllDays::up_to(30).count(), 25);
+    }
 
     #[test]
     fn all_days_iterator() {
@@ -176,6 +221,48 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[cfg(feature = "today")]
+    mod next_unlock {
+        use super::super::SERVER_UTC_OFFSET;
+        use crate::template::Day;
+        use chrono::{FixedOffset, TimeZone};
+
+        fn server_time(month: u32, day: u32, hour: u32) -> chrono::DateTime<FixedOffset> {
+            FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)
+                .unwrap()
+                .with_ymd_and_hms(2025, month, day, hour, 0, 0)
+                .unwrap()
+        }
+
+        #[test]
+        fn handles_december() {
+            let (day, unlock) = Day::next_unlock_after(server_time(12, 4, 23), 25).unwrap();
+            assert_eq!(day, day!(5));
+            assert_eq!(unlock, server_time(12, 5, 0));
+        }
+
+        #[test]
+        fn handles_before_december() {
+            let (day, unlock) = Day::next_unlock_after(server_time(11, 30, 12), 25).unwrap();
+            assert_eq!(day, day!(1));
+            assert_eq!(unlock, server_time(12, 1, 0));
+        }
+
+        #[test]
+        fn handles_after_advent() {
+            let (day, unlock) = Day::next_unlock_after(server_time(12, 25, 1), 25).unwrap();
+            assert_eq!(day, day!(1));
+            assert_eq!(unlock.to_string(), "2026-12-01 00:00:00 -05:00");
+        }
+
+        #[test]
+        fn handles_shorter_events() {
+            let (day, unlock) = Day::next_unlock_after(server_time(12, 12, 1), 12).unwrap();
+            assert_eq!(day, day!(1));
+            assert_eq!(unlock.to_string(), "2026-12-01 00:00:00 -05:00");
+        }
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,200 @@
+/// Helpers for loading puzzle inputs and examples from the `data` folder.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{Day, config};
+
+/// An error which can be returned when loading an input file.
+#[derive(Debug)]
+pub enum InputError {
+    /// The file does not exist.
+    NotFound(PathBuf),
+    /// The file exists, but is empty. This is the case for inputs that were scaffolded, but not downloaded yet.
+    Empty(PathBuf),
+    /// The file could not be read.
+    IO(PathBuf, io::Error),
+    /// The file only exists encrypted and could not be decrypted.
+    #[cfg(feature = "encrypted-inputs")]
+    Decrypt(PathBuf, super::crypto::CryptoError),
+}
+
+impl Error for InputError {}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound(path) => {
+                write!(f, "could not find input file \"{}\".", path.display())
+            }
+            InputError::Empty(path) => write!(
+                f,
+                "input file \"{}\" is empty. Did you forget to download it or to paste the example?",
+                path.display()
+            ),
+            InputError::IO(path, e) => {
+                write!(f, "could not read input file \"{}\": {e}", path.display())
+            }
+            #[cfg(feature = "encrypted-inputs")]
+            InputError::Decrypt(path, e) => {
+                write!(
+                    f,
+                    "could not decrypt input file \"{}\": {e}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+/// Normalises line endings to `\n` and makes sure the text ends with exactly one newline.
+/// Whitespace within and at the end of lines is kept as-is.
+#[must_use]
+pub fn normalize_input(s: &str) -> String {
+    let mut normalized = s.replace("\r\n", "\n");
+    normalized.truncate(normalized.trim_end_matches('\n').len());
+    normalized.push('\n');
+    normalized
+}
+
+fn get_data_path(folder: &str, filename: &str) -> PathBuf {
+    let paths = &config::get().paths;
+    let dir = match folder {
+        "inputs" => paths.inputs.clone(),
+        "examples" => paths.examples.clone(),
+        _ => paths.data.join(folder),
+    };
+    env::current_dir()
+        .unwrap_or_default()
+        .join(dir)
+        .join(filename)
+}
+
+/// Reads a file without any post-processing. Files that only contain whitespace are considered empty.
+/// With the `encrypted-inputs` feature, missing or empty files are decrypted from their `.enc` counterpart if it exists.
+pub fn read_path_raw(path: &Path) -> Result<String, InputError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => Some(contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(InputError::IO(path.to_path_buf(), e)),
+    };
+
+    // scaffolding may have created an empty file next to the encrypted one.
+    #[cfg(feature = "encrypted-inputs")]
+    let contents = match contents {
+        Some(contents) if !contents.trim().is_empty() => Some(contents),
+        _ if super::crypto::encrypted_path(path).exists() => Some(read_encrypted(path)?),
+        contents => contents,
+    };
+
+    let contents = contents.ok_or_else(|| InputError::NotFound(path.to_path_buf()))?;
+    if contents.trim().is_empty() {
+        return Err(InputError::Empty(path.to_path_buf()));
+    }
+
+    Ok(contents)
+}
+
+#[cfg(feature = "encrypted-inputs")]
+fn read_encrypted(path: &Path) -> Result<String, InputError> {
+    let bytes = super::crypto::read_encrypted(path)
+        .map_err(|e| InputError::Decrypt(path.to_path_buf(), e))?;
+    String::from_utf8(bytes).map_err(|e| {
+        InputError::IO(
+            path.to_path_buf(),
+            io::Error::new(io::ErrorKind::InvalidData, e),
+        )
+    })
+}
+
+/// Reads an input file to a string with normalised line endings and a single trailing newline.
+pub fn try_read_file(folder: &str, day: Day) -> Result<String, InputError> {
+    try_read_file_raw(folder, day).map(|s| normalize_input(&s))
+}
+
+/// Reads an input file to a string exactly as it is stored on disk.
+pub fn try_read_file_raw(folder: &str, day: Day) -> Result<String, InputError> {
+    read_path_raw(&get_data_path(folder, &format!("{day}.txt")))
+}
+
+/// Reads an input file to a string with normalised line endings and leading and trailing whitespace removed.
+pub fn try_read_file_trimmed(folder: &str, day: Day) -> Result<String, InputError> {
+    try_read_file(folder, day).map(|s| s.trim().to_string())
+}
+
+/// Reads an input file to a string with a part suffix, e.g. `01-2.txt`, with normalised line endings.
+pub fn try_read_file_part(folder: &str, day: Day, part: u8) -> Result<String, InputError> {
+    read_path_raw(&get_data_path(folder, &format!("{day}-{part}.txt"))).map(|s| normalize_input(&s))
+}
+
+/// Helper function that reads a text file to a string.
+///
+/// # Panics
+/// Panics if the file is missing, empty or unreadable.
+#[must_use]
+pub fn read_file(folder: &str, day: Day) -> String {
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+///
+/// # Panics
+/// Panics if the file is missing, empty or unreadable.
+#[must_use]
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    try_read_file_part(folder, day, part).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{InputError, normalize_input, read_path_raw};
+    use std::{env, fs};
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize_input("a\r\nb\r\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn normalizes_trailing_newlines() {
+        assert_eq!(normalize_input("1,2"), "1,2\n");
+        assert_eq!(normalize_input("1,2\n\n\n"), "1,2\n");
+        assert_eq!(normalize_input("a\n\nb"), "a\n\nb\n");
+    }
+
+    #[test]
+    fn keeps_trailing_spaces() {
+        assert_eq!(normalize_input("1 2 \n*   \n"), "1 2 \n*   \n");
+    }
+
+    #[test]
+    fn errors_for_missing_files() {
+        let path = env::temp_dir().join("aoc-input-tests-missing.txt");
+        let _ = fs::remove_file(&path);
+        assert!(matches!(read_path_raw(&path), Err(InputError::NotFound(_))));
+    }
+
+    #[test]
+    fn errors_for_empty_files() {
+        let path = env::temp_dir().join("aoc-input-tests-empty.txt");
+        fs::write(&path, "\n").unwrap();
+        let err = read_path_raw(&path).unwrap_err();
+        assert!(matches!(err, InputError::Empty(_)));
+        assert!(err.to_string().contains("aoc-input-tests-empty.txt"));
+    }
+
+    #[cfg(feature = "encrypted-inputs")]
+    #[test]
+    fn decrypts_instead_of_empty_files() {
+        let path = env::temp_dir().join("aoc-input-tests-scaffolded.txt");
+        fs::write(&path, "").unwrap();
+        fs::write(path.with_extension("txt.enc"), "not encrypted").unwrap();
+        assert!(matches!(read_path_raw(&path), Err(InputError::Decrypt(..))));
+    }
+}
@@ -0,0 +1,342 @@
+/// Renders the markdown puzzle descriptions written by aoc-cli to the terminal.
+use std::{
+    env,
+    process::{Command, Stdio},
+};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const DEFAULT_WIDTH: usize = 80;
+
+/// Returns the width of the terminal, falling back to 80 columns if it cannot be determined.
+pub fn terminal_width() -> usize {
+    if let Some(width) = env::var("COLUMNS").ok().and_then(|x| x.parse().ok()) {
+        return width;
+    }
+
+    // `stty` reads the terminal size from stdin, which is the terminal unless input is piped.
+    Command::new("stty")
+        .arg("size")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .and_then(|output| {
+            String::from_utf8(output.stdout)
+                .ok()?
+                .split_whitespace()
+                .nth(1)?
+                .parse()
+                .ok()
+        })
+        .filter(|width| *width > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    ListItem(String),
+    Code(Vec<String>),
+}
+
+fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut paragraph: Option<Block> = None;
+    let mut code: Option<Vec<String>> = None;
+
+    for line in markdown.lines() {
+        if let Some(lines) = code.as_mut() {
+            if line.trim_start().starts_with("```") {
+                blocks.push(Block::Code(code.take().unwrap_or_default()));
+            } else {
+                lines.push(line.to_string());
+            }
+            continue;
+        }
+
+        let trimmed = line.trim();
+
+        let is_heading = trimmed.starts_with('#')
+            || (trimmed.trim_start_matches('\\').starts_with("--- ") && trimmed.ends_with(" ---"));
+        let is_underline = !trimmed.is_empty() && trimmed.chars().all(|c| c == '-' || c == '=');
+        let list_item = trimmed
+            .strip_prefix("* ")
+            .or_else(|| trimmed.strip_prefix("- "));
+
+        if trimmed.is_empty()
+            || is_heading
+            || is_underline
+            || list_item.is_some()
+            || trimmed.starts_with("```")
+        {
+            blocks.extend(paragraph.take());
+        }
+
+        if trimmed.starts_with("```") {
+            code = Some(vec![]);
+        } else if is_heading {
+            let heading = trimmed
+                .trim_start_matches('#')
+                .trim_start_matches('\\')
+                .trim();
+            blocks.push(Block::Heading(heading.to_string()));
+        } else if let Some(item) = list_item {
+            paragraph = Some(Block::ListItem(item.to_string()));
+        } else if !trimmed.is_empty() && !is_underline {
+            match paragraph.as_mut() {
+                Some(Block::Paragraph(text) | Block::ListItem(text)) => {
+                    text.push(' ');
+                    text.push_str(trimmed);
+                }
+                _ => paragraph = Some(Block::Paragraph(trimmed.to_string())),
+            }
+        }
+    }
+
+    blocks.extend(paragraph);
+    blocks.extend(code.map(Block::Code));
+    blocks
+}
+
+/// A word of inline text together with its style.
+struct Word {
+    text: String,
+    em: bool,
+    code: bool,
+    /// Whether the word directly follows the previous one without whitespace, e.g. punctuation after emphasis.
+    joined: bool,
+}
+
+#[derive(Default)]
+struct InlineParser {
+    words: Vec<Word>,
+    current: String,
+    em: bool,
+    code: bool,
+    joined: bool,
+}
+
+impl InlineParser {
+    fn push_word(&mut self) {
+        if !self.current.is_empty() {
+            self.words.push(Word {
+                text: std::mem::take(&mut self.current),
+                em: self.em,
+                code: self.code,
+                joined: self.joined,
+            });
+            self.joined = true;
+        }
+    }
+
+    fn push_whitespace(&mut self) {
+        self.push_word();
+        self.joined = false;
+    }
+}
+
+/// Splits inline markdown into styled words. `*em*` and `**strong**` are treated alike as AoC only uses `<em>`.
+fn parse_inline(text: &str) -> Vec<Word> {
+    let mut parser = InlineParser::default();
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' if i + 1 < chars.len() => {
+                parser.current.push(chars[i + 1]);
+                i += 1;
+            }
+            '*' => {
+                parser.push_word();
+                parser.em = !parser.em;
+                while chars.get(i + 1) == Some(&'*') {
+                    i += 1;
+                }
+            }
+            '`' => {
+                parser.push_word();
+                parser.code = !parser.code;
+            }
+            '[' if !parser.code => {
+                // render links as their text only.
+                let rest: String = chars[i + 1..].iter().collect();
+                if let Some((label, url)) = rest.split_once("](")
+                    && let Some(url_end) = url.find(')')
+                    && !label.contains('[')
+                {
+                    parser.current.push_str(label);
+                    i += label.chars().count() + 2 + url[..url_end].chars().count() + 1;
+                } else {
+                    parser.current.push(c);
+                }
+            }
+            c if c.is_whitespace() => parser.push_whitespace(),
+            c => parser.current.push(c),
+        }
+        i += 1;
+    }
+
+    parser.push_word();
+    parser.words
+}
+
+fn style_word(word: &Word) -> String {
+    match (word.em, word.code) {
+        (false, false) => word.text.clone(),
+        (true, false) => format!("{ANSI_BOLD}{}{ANSI_RESET}", word.text),
+        (false, true) => format!("{ANSI_ITALIC}{}{ANSI_RESET}", word.text),
+        (true, true) => format!("{ANSI_BOLD}{ANSI_ITALIC}{}{ANSI_RESET}", word.text),
+    }
+}
+
+/// Wraps styled words to `width` visible columns. Every line but the first is prefixed with `indent`.
+fn wrap(words: &[Word], width: usize, first_prefix: &str, indent: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first_prefix.to_string();
+    let mut line_width = first_prefix.chars().count();
+    let mut is_line_empty = true;
+
+    for (i, word) in words.iter().enumerate() {
+        if word.joined && !is_line_empty {
+            line.push_str(&style_word(word));
+            line_width += word.text.chars().count();
+            continue;
+        }
+
+        // keep words that are joined to this one on the same line.
+        let unit_width: usize = words[i..]
+            .iter()
+            .enumerate()
+            .take_while(|(j, w)| *j == 0 || w.joined)
+            .map(|(_, w)| w.text.chars().count())
+            .sum();
+
+        if !is_line_empty && line_width + 1 + unit_width > width {
+            lines.push(std::mem::replace(&mut line, indent.to_string()));
+            line_width = indent.chars().count();
+            is_line_empty = true;
+        }
+        if !is_line_empty {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(&style_word(word));
+        line_width += word.text.chars().count();
+        is_line_empty = false;
+    }
+
+    lines.push(line);
+    lines
+}
+
+fn render_code(lines: &[String]) -> Vec<String> {
+    let inner_width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let border = "─".repeat(inner_width + 2);
+
+    let mut rendered = vec![format!("┌{border}┐")];
+    rendered.extend(lines.iter().map(|line| {
+        let padding = " ".repeat(inner_width - line.chars().count());
+        format!("│ {line}{padding} │")
+    }));
+    rendered.push(format!("└{border}┘"));
+    rendered
+}
+
+/// Renders markdown to a string with ANSI styles, wrapping text to `width` columns.
+pub fn render(markdown: &str, width: usize) -> String {
+    let width = width.max(20);
+    let mut lines: Vec<String> = vec![];
+    let mut in_list = false;
+
+    for block in parse_blocks(markdown) {
+        // separate a list from whatever follows it.
+        if in_list && !matches!(block, Block::ListItem(_)) {
+            lines.push(String::new());
+        }
+        in_list = matches!(block, Block::ListItem(_));
+
+        match block {
+            Block::Heading(text) => {
+                lines.push(format!("{ANSI_BOLD}{text}{ANSI_RESET}"));
+                lines.push(String::new());
+            }
+            Block::Paragraph(text) => {
+                lines.extend(wrap(&parse_inline(&text), width, "", ""));
+                lines.push(String::new());
+            }
+            Block::ListItem(text) => {
+                lines.extend(wrap(&parse_inline(&text), width, "  • ", "    "));
+            }
+            Block::Code(code) => {
+                lines.extend(render_code(&code));
+                lines.push(String::new());
+            }
+        }
+    }
+
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::render;
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn renders_headings() {
+        let rendered = render("\\--- Day 1: Foo ---\n----------\n\nbar", 80);
+        assert_eq!(
+            rendered,
+            format!("{ANSI_BOLD}--- Day 1: Foo ---{ANSI_RESET}\n\nbar")
+        );
+    }
+
+    #[test]
+    fn renders_emphasis_and_code() {
+        let rendered = render("produces `*142*` and *more*.", 80);
+        assert_eq!(
+            rendered,
+            format!(
+                "produces {ANSI_BOLD}{ANSI_ITALIC}142{ANSI_RESET} and {ANSI_BOLD}more{ANSI_RESET}."
+            )
+        );
+    }
+
+    #[test]
+    fn renders_links_as_text() {
+        assert_eq!(
+            render("see [the site](https://example.com).", 80),
+            "see the site."
+        );
+    }
+
+    #[test]
+    fn wraps_paragraphs() {
+        let rendered = render("aaaa bbbb cccc dddd eeee ffff", 20);
+        assert_eq!(rendered, "aaaa bbbb cccc dddd\neeee ffff");
+    }
+
+    #[test]
+    fn renders_code_blocks_in_a_box() {
+        let rendered = render("For example:\n\n```\n1abc2\nxy\n```\n", 80);
+        assert_eq!(
+            rendered,
+            "For example:\n\n┌───────┐\n│ 1abc2 │\n│ xy    │\n└───────┘"
+        );
+    }
+
+    #[test]
+    fn renders_list_items() {
+        let rendered = render("* one\n* two\n\nthree", 80);
+        assert_eq!(rendered, "  • one\n  • two\n\nthree");
+    }
+}
@@ -1,13 +1,30 @@
-use std::{env, fs};
-
 pub mod aoc_cli;
+pub mod cli;
 pub mod commands;
+pub mod config;
+#[cfg(feature = "encrypted-inputs")]
+pub mod crypto;
+pub mod ocr;
+pub mod phases;
+pub mod property;
 pub mod runner;
+pub mod scale;
+pub mod submissions;
+pub mod visualize;
 
+pub use answer::*;
 pub use day::*;
+pub use input::*;
+pub use phases::phase;
+pub use rng::Rng;
 
+mod answer;
 mod day;
+mod input;
+mod markdown;
+mod puzzle;
 mod readme_benchmarks;
+mod rng;
 mod run_multi;
 mod timings;
 
@@ -15,43 +32,43 @@ pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
-#[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
-#[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Parts can also have several implementations, e.g. a fast one and a brute-force one to check it against.
+/// The runner checks that all of them find the same answer, and benchmarks them side by side with `--time`:
+///
+/// ```ignore
+/// advent_of_code::solution!(2, 1 => [part_one, part_one_brute_force], 2 => [part_two]);
+/// ```
+///
+/// An input generator can be passed last, which `--scale` uses to time the parts on inputs of growing size,
+/// see [`scale`](crate::template::scale):
+///
+/// ```ignore
+/// advent_of_code::solution!(8; generator = generate);
+/// ```
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(; generator = $gen:expr)?) => {
+        $crate::solution!(@impl $day, [$($gen)?], [[part_one], 1] [[part_two], 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr, 1 $(; generator = $gen:expr)?) => {
+        $crate::solution!(@impl $day, [$($gen)?], [[part_one], 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr, 2 $(; generator = $gen:expr)?) => {
+        $crate::solution!(@impl $day, [$($gen)?], [[part_two], 2]);
     };
+    ($day:expr, $( $part:literal => [ $($func:expr),+ $(,)? ] ),+ $(,)? $(; generator = $gen:expr)?) => {
+        $crate::solution!(@impl $day, [$($gen)?], $( [[$($func),+], $part] )+);
+    };
+
+    (@generator) => { None };
+    (@generator $gen:expr) => { Some($gen) };
+    (@first $first:expr $(, $rest:expr)*) => { $first };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, [$($gen:expr)?], $( [[$($func:expr),+], $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -61,8 +78,35 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let options = RunOptions::from_env().unwrap_or_else(|e| exit_with_error(e));
+            if options.help {
+                print!("{}", RunOptions::usage(DAY));
+                return;
+            }
+            if let Err(e) = $crate::template::config::init() {
+                exit_with_error(e);
+            }
+            if options.scale {
+                use $crate::template::scale::*;
+                let generator: Option<Generator> = $crate::solution!(@generator $($gen)?);
+                let parts: &[ScalePart] = &[$(
+                    ($part, &|input: &str| {
+                        std::hint::black_box($crate::solution!(@first $($func),+)(input));
+                    })
+                ),*];
+                run_scale(DAY, generator, parts, &options).unwrap_or_else(|e| exit_with_error(e));
+                return;
+            }
+            let input = options.read_input(DAY).unwrap_or_else(|e| exit_with_error(e));
+            let mut results = vec![];
+            $(
+                if options.runs_part($part) {
+                    let implementations: &[PartFn<&str, _>] = &[$( (stringify!($func), &$func) ),+];
+                    let result = run_part(implementations, &input, DAY, $part, &options);
+                    results.push(result.unwrap_or_else(|e| exit_with_error(e)));
+                }
+            )*
+            print_results(DAY, &options, &results);
         }
     };
 }
@@ -0,0 +1,271 @@
+//! Recognition of the block letters that some puzzles draw their answers with.
+//! Both fonts used by Advent of Code are supported: letters that are 6 pixels high (e.g. 2016 day 8, 2019 day 11)
+//! and letters that are 10 pixels high (2018 day 10).
+
+/// Letters of the 6 pixels high font. `I` and `Y` are narrower and wider than the other letters.
+const SMALL_FONT: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Letters of the 10 pixels high font.
+const LARGE_FONT: &[(char, [&str; 10])] = &[
+    (
+        'A',
+        [
+            "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'B',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+            "#....#", "#####.",
+        ],
+    ),
+    (
+        'C',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#....#", ".####.",
+        ],
+    ),
+    (
+        'E',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'F',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'G',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+            "#...##", ".###.#",
+        ],
+    ),
+    (
+        'H',
+        [
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'J',
+        [
+            "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+            "#...#.", ".###..",
+        ],
+    ),
+    (
+        'K',
+        [
+            "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+            "#...#.", "#....#",
+        ],
+    ),
+    (
+        'L',
+        [
+            "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'N',
+        [
+            "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+            "#...##", "#....#",
+        ],
+    ),
+    (
+        'P',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'R',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'X',
+        [
+            "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'Z',
+        [
+            "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+];
+
+/// Decodes letters drawn as text, e.g. the `Display` output of an answer.
+/// Spaces and `.` are unlit pixels, every other character is lit.
+/// Returns `None` if the text is not made of letters of one of the fonts.
+#[must_use]
+pub fn decode(art: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = art.lines().map(parse_row).collect();
+    decode_grid(&rows)
+}
+
+/// Decodes letters drawn in a grid of pixels, with `true` for lit pixels. Rows may differ in length.
+/// Returns `None` if the grid is not made of letters of one of the fonts.
+#[must_use]
+pub fn decode_grid<R: AsRef<[bool]>>(rows: &[R]) -> Option<String> {
+    let is_blank = |row: &&R| !row.as_ref().contains(&true);
+    let first = rows.iter().position(|row| !is_blank(&row))?;
+    let last = rows.iter().rposition(|row| !is_blank(&row))?;
+    let rows: Vec<&[bool]> = rows[first..=last].iter().map(AsRef::as_ref).collect();
+
+    let width = rows.iter().map(|row| row.len()).max()?;
+    let pixel = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+    let is_blank_column = |x: usize| (0..rows.len()).all(|y| !pixel(x, y));
+
+    let mut text = String::new();
+    let mut x = 0;
+
+    // letters are separated by at least one blank column, and none of them contain one.
+    while x < width {
+        if is_blank_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !is_blank_column(x) {
+            x += 1;
+        }
+
+        let letter: Vec<Vec<bool>> = (0..rows.len())
+            .map(|y| (start..x).map(|x| pixel(x, y)).collect())
+            .collect();
+        text.push(match rows.len() {
+            6 => find_letter(SMALL_FONT, &letter)?,
+            10 => find_letter(LARGE_FONT, &letter)?,
+            _ => return None,
+        });
+    }
+
+    Some(text)
+}
+
+fn parse_row(row: &str) -> Vec<bool> {
+    row.chars()
+        .map(|c| !c.is_whitespace() && c != '.')
+        .collect()
+}
+
+fn find_letter<const H: usize>(font: &[(char, [&str; H])], letter: &[Vec<bool>]) -> Option<char> {
+    font.iter()
+        .find(|(_, glyph)| {
+            glyph
+                .iter()
+                .map(|row| parse_row(row))
+                .eq(letter.iter().cloned())
+        })
+        .map(|(c, _)| *c)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{LARGE_FONT, SMALL_FONT, decode, decode_grid};
+
+    /// Draws a word in the given font, with one blank column between letters.
+    fn draw<const H: usize>(font: &[(char, [&str; H])], word: &str) -> String {
+        let glyphs: Vec<&[&str; H]> = word
+            .chars()
+            .map(|c| &font.iter().find(|(x, _)| *x == c).unwrap().1)
+            .collect();
+
+        (0..H)
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|glyph| glyph[y])
+                    .collect::<Vec<_>>()
+                    .join(".")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn decodes_small_letters() {
+        let art = "\
+            ####.####\n\
+            #.......#\n\
+            ###....#.\n\
+            #.....#..\n\
+            #....#...\n\
+            ####.####";
+        assert_eq!(decode(art).as_deref(), Some("EZ"));
+
+        let word: String = SMALL_FONT.iter().map(|(c, _)| c).collect();
+        assert_eq!(decode(&draw(SMALL_FONT, &word)), Some(word));
+    }
+
+    #[test]
+    fn decodes_large_letters() {
+        let word: String = LARGE_FONT.iter().map(|(c, _)| c).collect();
+        assert_eq!(decode(&draw(LARGE_FONT, &word)), Some(word));
+    }
+
+    #[test]
+    fn decodes_other_pixel_characters() {
+        let art = draw(SMALL_FONT, "HI").replace('#', "█").replace('.', " ");
+        assert_eq!(decode(&format!("\n{art}\n\n")).as_deref(), Some("HI"));
+    }
+
+    #[test]
+    fn decodes_grids() {
+        let rows: Vec<Vec<bool>> = draw(SMALL_FONT, "OK")
+            .lines()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect();
+        assert_eq!(decode_grid(&rows).as_deref(), Some("OK"));
+    }
+
+    #[test]
+    fn rejects_unknown_letters() {
+        assert_eq!(decode(&draw(SMALL_FONT, "AB").replacen('#', ".", 1)), None);
+        assert_eq!(decode("#\n#\n#"), None);
+        assert_eq!(decode("12345"), None);
+        assert_eq!(decode(""), None);
+    }
+}
@@ -0,0 +1,104 @@
+/// Named timers for the phases of a part, e.g. parsing and searching.
+/// Phases are only recorded while the runner benchmarks a part with `--time`, in a separate pass after the timed runs,
+/// so that recording them does not slow the timed runs down. Otherwise, starting a timer is a single thread-local check.
+/// Only phases entered on the thread that runs the part are recorded.
+use std::{
+    cell::RefCell,
+    time::{Duration, Instant},
+};
+
+thread_local! {
+    /// The phases recorded so far, or `None` while not recording.
+    static PHASES: RefCell<Option<Vec<Phase>>> = const { RefCell::new(None) };
+}
+
+/// The time spent in a phase, in the order phases were first entered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Phase {
+    pub name: &'static str,
+    pub duration: Duration,
+    /// How often the phase was entered.
+    pub count: u64,
+}
+
+/// Times a phase until the returned timer is dropped. Time spent in phases with the same name is added up,
+/// so a phase must not be entered again while it is running, e.g. from within recursion.
+///
+/// ```
+/// # use advent_of_code::template::phase;
+/// let _t = phase("sort pairs");
+/// ```
+pub fn phase(name: &'static str) -> PhaseTimer {
+    PhaseTimer {
+        name,
+        start: PHASES.with_borrow(Option::is_some).then(Instant::now),
+    }
+}
+
+/// Records the time of its phase when dropped, see [`phase`].
+#[must_use = "the phase ends when the timer is dropped"]
+pub struct PhaseTimer {
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+impl Drop for PhaseTimer {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            record(self.name, start.elapsed());
+        }
+    }
+}
+
+fn record(name: &'static str, duration: Duration) {
+    PHASES.with_borrow_mut(|phases| {
+        let Some(phases) = phases else {
+            return;
+        };
+        match phases.iter_mut().find(|phase| phase.name == name) {
+            Some(phase) => {
+                phase.duration += duration;
+                phase.count += 1;
+            }
+            None => phases.push(Phase {
+                name,
+                duration,
+                count: 1,
+            }),
+        }
+    });
+}
+
+/// Discards the phases recorded so far and starts recording on the current thread.
+pub(crate) fn start() {
+    PHASES.set(Some(vec![]));
+}
+
+/// Stops recording and returns the phases recorded since [`start`].
+pub(crate) fn finish() -> Vec<Phase> {
+    PHASES.take().unwrap_or_default()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{finish, phase, start};
+
+    #[test]
+    fn records_phases_only_when_started() {
+        drop(phase("ignored"));
+
+        start();
+        for _ in 0..3 {
+            let _t = phase("loop");
+        }
+        drop(phase("after"));
+        let phases = finish();
+        drop(phase("ignored"));
+
+        let names: Vec<_> = phases.iter().map(|p| (p.name, p.count)).collect();
+        assert_eq!(names, [("loop", 3), ("after", 1)]);
+        assert!(finish().is_empty());
+    }
+}
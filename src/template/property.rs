@@ -0,0 +1,277 @@
+/// Randomised differential tests, which compare a part against a simpler reference implementation on generated inputs.
+/// Inputs that make them disagree are shrunk to a small example before the test fails.
+use std::{
+    env,
+    fmt::Debug,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::Rng;
+
+/// Compares two implementations of a part on inputs built by a generator.
+///
+/// The generator receives a [`Rng`] and a size, which grows with every case and should bound the size of the input,
+/// e.g. the number of lines. Generators must only produce valid inputs, as panics are not caught.
+///
+/// ```ignore
+/// Differential::new(generate).check(part_one, part_one_brute_force);
+/// ```
+pub struct Differential<G> {
+    generate: G,
+    cases: u32,
+    max_size: usize,
+    seed: Option<u64>,
+}
+
+/// A generated input, and the choices and size it was built from.
+struct Case {
+    input: String,
+    choices: Vec<u64>,
+    size: usize,
+}
+
+/// How many inputs are tried while shrinking.
+const SHRINK_BUDGET: usize = 2000;
+
+impl<G: Fn(&mut Rng, usize) -> String> Differential<G> {
+    /// Creates a test for inputs built by `generate`.
+    pub fn new(generate: G) -> Self {
+        Self {
+            generate,
+            cases: 100,
+            max_size: 100,
+            seed: None,
+        }
+    }
+
+    /// How many inputs are generated. Defaults to 100.
+    #[must_use]
+    pub fn cases(mut self, cases: u32) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// The size passed to the generator for the last case. Defaults to 100.
+    #[must_use]
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Uses a fixed seed. Otherwise, the seed is read from the `AOC_SEED` environment variable or picked at random.
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Checks that `part` and `reference` return the same result on every generated input.
+    ///
+    /// # Panics
+    /// Panics with the shrunk input and both results if they disagree.
+    pub fn check<T: PartialEq + Debug>(
+        &self,
+        part: impl Fn(&str) -> T,
+        reference: impl Fn(&str) -> T,
+    ) {
+        let seed = self.seed.unwrap_or_else(default_seed);
+        let mut rng = Rng::new(seed);
+        let fails = |case: &Case| part(&case.input) != reference(&case.input);
+
+        for i in 0..self.cases {
+            let size =
+                1 + i as usize * self.max_size.saturating_sub(1) / self.cases.max(2) as usize;
+            let case_seed = rng.next_u64();
+            let case = self.build(Rng::new(case_seed), size);
+            if !fails(&case) {
+                continue;
+            }
+
+            let (case, steps) = self.shrink(case, fails);
+            panic!(
+                "part and reference disagree on case {} (seed {seed}, shrunk in {steps} steps).\n\
+                 input:\n{}\n\
+                 part:      {:?}\n\
+                 reference: {:?}\n\
+                 Run again with AOC_SEED={seed} to reproduce.",
+                i + 1,
+                case.input,
+                part(&case.input),
+                reference(&case.input),
+            );
+        }
+    }
+
+    fn build(&self, mut rng: Rng, size: usize) -> Case {
+        let input = (self.generate)(&mut rng, size);
+        Case {
+            input,
+            choices: rng.into_choices(),
+            size,
+        }
+    }
+
+    /// Replays the case with smaller sizes, fewer choices and smaller choices for as long as it keeps failing.
+    /// Returns the smallest failing case and how many times it was made smaller.
+    fn shrink(&self, mut case: Case, fails: impl Fn(&Case) -> bool) -> (Case, usize) {
+        let mut attempts = 0;
+        let mut steps = 0;
+        let mut try_shrink = |case: &mut Case, size: usize, choices: Vec<u64>| {
+            if attempts >= SHRINK_BUDGET {
+                return false;
+            }
+            attempts += 1;
+
+            let candidate = self.build(Rng::replay(choices), size);
+            let smaller = (candidate.size, candidate.choices.len(), &candidate.choices)
+                < (case.size, case.choices.len(), &case.choices);
+            if smaller && fails(&candidate) {
+                *case = candidate;
+                steps += 1;
+                true
+            } else {
+                false
+            }
+        };
+
+        loop {
+            let before = (case.size, case.choices.clone());
+
+            // smallest failing size. Sizes start at 1 like in `check`, as generators may not handle 0.
+            let (mut low, mut high) = (1, case.size);
+            while low < high {
+                let mid = low + (high - low) / 2;
+                let choices = case.choices.clone();
+                if try_shrink(&mut case, mid, choices) {
+                    high = case.size;
+                } else {
+                    low = mid + 1;
+                }
+            }
+
+            // fewer choices, e.g. fewer lines.
+            for chunk in [8, 4, 2, 1] {
+                let mut i = 0;
+                while i + chunk <= case.choices.len() {
+                    let mut choices = case.choices.clone();
+                    choices.drain(i..i + chunk);
+                    let size = case.size;
+                    if !try_shrink(&mut case, size, choices) {
+                        i += 1;
+                    }
+                }
+            }
+
+            // smaller choices, e.g. smaller numbers.
+            for i in 0..case.choices.len() {
+                let (mut low, mut high) = (0, case.choices.get(i).copied().unwrap_or(0));
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    let mut choices = case.choices.clone();
+                    choices[i] = mid;
+                    let size = case.size;
+                    if try_shrink(&mut case, size, choices) {
+                        high = case.choices.get(i).copied().unwrap_or(0).min(mid);
+                    } else {
+                        low = mid + 1;
+                    }
+                }
+            }
+
+            if (case.size, &case.choices) == (before.0, &before.1) {
+                return (case, steps);
+            }
+        }
+    }
+}
+
+/// Reads the seed from `AOC_SEED`, or derives one from the current time.
+fn default_seed() -> u64 {
+    env::var("AOC_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_nanos() as u64)
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use super::Differential;
+    use crate::template::Rng;
+
+    fn numbers(rng: &mut Rng, size: usize) -> String {
+        (0..rng.below(size as u64 + 1))
+            .map(|_| format!("{}\n", rng.below(1000)))
+            .collect()
+    }
+
+    fn sum(input: &str) -> u64 {
+        input.lines().map(|line| line.parse::<u64>().unwrap()).sum()
+    }
+
+    #[test]
+    fn passes_when_implementations_agree() {
+        Differential::new(numbers).seed(1).check(sum, |input| {
+            input
+                .lines()
+                .fold(0, |acc, line| acc + line.parse::<u64>().unwrap())
+        });
+    }
+
+    #[test]
+    fn shrinks_failing_inputs() {
+        // the "part" ignores numbers of 3 digits or more.
+        let part = |input: &str| {
+            input
+                .lines()
+                .map(|line| line.parse::<u64>().unwrap())
+                .filter(|x| *x < 100)
+                .sum::<u64>()
+        };
+
+        let error = panic::catch_unwind(AssertUnwindSafe(|| {
+            Differential::new(numbers).seed(1).check(part, sum);
+        }))
+        .expect_err("the check should fail");
+        let message = error.downcast_ref::<String>().unwrap();
+
+        assert!(message.contains("input:\n100\n\n"), "{message}");
+        assert!(
+            message.contains("part:      0\nreference: 100\n"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn shrinks_sizes_down_to_one() {
+        // at least one line, so the generator panics for size 0.
+        let lines = |rng: &mut Rng, size: usize| {
+            (0..rng.range(1..=size as u64))
+                .map(|_| format!("{}\n", rng.below(10)))
+                .collect::<String>()
+        };
+
+        let error = panic::catch_unwind(AssertUnwindSafe(|| {
+            Differential::new(lines).seed(7).check(
+                |input| input.lines().count() + 1,
+                |input| input.lines().count(),
+            );
+        }))
+        .expect_err("the check should fail");
+        let message = error.downcast_ref::<String>().unwrap();
+
+        assert!(message.contains("(seed 7, "), "{message}");
+        assert!(message.contains("input:\n0\n\n"), "{message}");
+        assert!(
+            message.contains("part:      2\nreference: 1\n"),
+            "{message}"
+        );
+    }
+}
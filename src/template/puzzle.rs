@@ -0,0 +1,145 @@
+/// Helpers for puzzle descriptions that were downloaded via aoc-cli.
+use std::{env, fs};
+
+use crate::template::{Day, config};
+
+/// A puzzle description, split into its parts.
+#[derive(Clone, Debug, Default)]
+pub struct Puzzle {
+    pub title: Option<String>,
+    pub parts: Vec<String>,
+}
+
+impl Puzzle {
+    /// Reads the cached description for a day from the puzzles folder, if it has been downloaded.
+    pub fn read(day: Day) -> Option<Self> {
+        read_markdown(day).map(|s| Self::parse(&s))
+    }
+
+    /// Whether the description of part two has been downloaded.
+    pub fn has_part_two(&self) -> bool {
+        self.parts.len() > 1
+    }
+
+    /// Parses the markdown written by aoc-cli. Parts are delimited by the `--- Part Two ---` heading.
+    pub fn parse(markdown: &str) -> Self {
+        let mut puzzle = Self::default();
+        let mut current: Option<String> = None;
+
+        for line in markdown.lines() {
+            if let Some(heading) = parse_heading(line) {
+                if let Some(part) = current.take() {
+                    puzzle.parts.push(part);
+                }
+                if let Some((_, title)) = heading.split_once(": ") {
+                    puzzle.title = Some(title.to_string());
+                }
+                current = Some(String::new());
+                continue;
+            }
+
+            // skip the setext underline that follows each heading.
+            if !line.is_empty() && line.chars().all(|c| c == '-') {
+                continue;
+            }
+
+            let part = current.get_or_insert_with(String::new);
+            part.push_str(line);
+            part.push('\n');
+        }
+
+        if let Some(part) = current {
+            puzzle.parts.push(part);
+        }
+
+        puzzle
+    }
+
+    /// Best-effort guess of the example answer for a part: the last emphasised number in its text.
+    pub fn example_answer(&self, part: u8) -> Option<String> {
+        let text = self.parts.get(usize::from(part).checked_sub(1)?)?;
+        let text = text.split("Your puzzle answer was").next()?;
+
+        text.split('*')
+            .skip(1)
+            .step_by(2)
+            .map(|s| s.trim_matches(|c: char| c == '`' || c.is_whitespace()))
+            .filter(|s| s.parse::<i128>().is_ok())
+            .last()
+            .map(str::to_string)
+    }
+}
+
+/// Reads the raw markdown of a cached puzzle description.
+pub fn read_markdown(day: Day) -> Option<String> {
+    let cwd = env::current_dir().ok()?;
+    let filepath = cwd
+        .join(&config::get().paths.puzzles)
+        .join(format!("{day}.md"));
+    fs::read_to_string(filepath).ok()
+}
+
+/// Returns the markdown of a single part, including its heading.
+pub fn part_markdown(markdown: &str, part: u8) -> Option<String> {
+    let index = usize::from(part).checked_sub(1)?;
+    let mut sections: Vec<String> = vec![];
+
+    for line in markdown.lines() {
+        if parse_heading(line).is_some() || sections.is_empty() {
+            sections.push(String::new());
+        }
+        if let Some(section) = sections.last_mut() {
+            section.push_str(line);
+            section.push('\n');
+        }
+    }
+
+    sections.into_iter().nth(index)
+}
+
+fn parse_heading(line: &str) -> Option<&str> {
+    line.trim_start_matches('\\')
+        .trim()
+        .strip_prefix("--- ")?
+        .strip_suffix(" ---")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Puzzle, part_markdown};
+
+    const PUZZLE: &str = "\\--- Day 2: Gift Shop ---\n----------\n\nAdding up all the invalid IDs produces `*1227775554*`.\n\nYour puzzle answer was `12`.\n\n\\--- Part Two ---\n----------\n\nNow it is *4174379265*.\n";
+
+    #[test]
+    fn parses_title_and_parts() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(puzzle.title.as_deref(), Some("Gift Shop"));
+        assert_eq!(puzzle.parts.len(), 2);
+    }
+
+    #[test]
+    fn finds_example_answers() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(puzzle.example_answer(1).as_deref(), Some("1227775554"));
+        assert_eq!(puzzle.example_answer(2).as_deref(), Some("4174379265"));
+        assert_eq!(puzzle.example_answer(3), None);
+    }
+
+    #[test]
+    fn handles_part_one_only() {
+        let puzzle = Puzzle::parse("\\--- Day 1: Foo ---\n----------\n\nbar *baz*\n");
+        assert_eq!(puzzle.parts.len(), 1);
+        assert_eq!(puzzle.example_answer(1), None);
+    }
+
+    #[test]
+    fn splits_part_markdown() {
+        let part_two = part_markdown(PUZZLE, 2).unwrap();
+        assert!(part_two.starts_with("\\--- Part Two ---\n"));
+        assert!(part_two.contains("4174379265"));
+        assert!(!part_markdown(PUZZLE, 1).unwrap().contains("Part Two"));
+        assert_eq!(part_markdown(PUZZLE, 3), None);
+    }
+}
@@ -2,12 +2,11 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::Day;
 use crate::template::timings::Timings;
+use crate::template::{Day, config};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -65,12 +64,20 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
+        // parts with several implementations show the name of the fastest one.
+        let cell = |duration: Option<String>, implementation: &Option<String>| {
+            let duration = duration.unwrap_or_else(|| "-".into());
+            match implementation {
+                Some(name) => format!("`{duration}` (`{name}`)"),
+                None => format!("`{duration}`"),
+            }
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            cell(timing.part_1, &timing.implementations[0]),
+            cell(timing.part_2, &timing.implementations[1])
         ));
     }
 
@@ -89,7 +96,7 @@ fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+    let path = &config::get().readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
     update_content(&mut readme, timings, total_millis)?;
@@ -97,7 +104,7 @@ pub fn update(timings: Timings) -> Result<(), Error> {
     Ok(())
 }
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
     use super::{MARKER, update_content};
     use crate::{day, template::timings::Timing, template::timings::Timings};
@@ -110,18 +117,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    machines: [None, None],
+                    implementations: [None, None],
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    machines: [None, None],
+                    implementations: [Some("part_one".into()), None],
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    machines: [None, None],
+                    implementations: [None, None],
                 },
             ],
         }
@@ -145,7 +158,7 @@ mod tests {
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
         update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        assert!(s.contains("## Benchmarks"));
     }
 
     #[test]
@@ -170,7 +183,7 @@ mod tests {
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
             "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` (`part_one`) | `40ms` |",
             "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
@@ -0,0 +1,140 @@
+/// A small pseudo-random generator for building test inputs, see [`property`](crate::template::property).
+/// Every value is derived from a sequence of recorded choices, so that failing inputs can be replayed and shrunk.
+use std::ops::RangeInclusive;
+
+/// Draws random values for input generators. Smaller choices give smaller values, which is what shrinking relies on.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    source: Source,
+    choices: Vec<u64>,
+}
+
+#[derive(Clone, Debug)]
+enum Source {
+    /// splitmix64, see <https://prng.di.unimi.it/splitmix64.c>.
+    Random(u64),
+    /// Replays earlier choices, then draws zeros.
+    Replay(Vec<u64>, usize),
+}
+
+impl Rng {
+    /// Creates a generator that always draws the same values for the same seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            source: Source::Random(seed),
+            choices: vec![],
+        }
+    }
+
+    /// Creates a generator that draws `choices`, followed by zeros once they run out.
+    pub(crate) fn replay(choices: Vec<u64>) -> Self {
+        Self {
+            source: Source::Replay(choices, 0),
+            choices: vec![],
+        }
+    }
+
+    /// Returns the choices that were drawn so far.
+    pub(crate) fn into_choices(self) -> Vec<u64> {
+        self.choices
+    }
+
+    /// Draws a uniformly distributed number.
+    pub fn next_u64(&mut self) -> u64 {
+        let choice = match &mut self.source {
+            Source::Random(state) => {
+                *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                let mut z = *state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                z ^ (z >> 31)
+            }
+            Source::Replay(choices, position) => {
+                let choice = choices.get(*position).copied().unwrap_or(0);
+                *position += 1;
+                choice
+            }
+        };
+        self.choices.push(choice);
+        choice
+    }
+
+    /// Draws a number in `0..n`.
+    ///
+    /// # Panics
+    /// Panics if `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "cannot draw a number below 0");
+        self.next_u64() % n
+    }
+
+    /// Draws a number in `range`.
+    ///
+    /// # Panics
+    /// Panics if the range is empty.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(
+            start <= end,
+            "cannot draw from the empty range {start}..={end}"
+        );
+        match (end - start).checked_add(1) {
+            Some(n) => start + self.below(n),
+            None => self.next_u64(),
+        }
+    }
+
+    /// Draws `true` or `false` with the same probability.
+    pub fn bool(&mut self) -> bool {
+        self.below(2) == 1
+    }
+
+    /// Draws one of `items`.
+    ///
+    /// # Panics
+    /// Panics if `items` is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    /// Shuffles `items` in place.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn draws_the_same_values_for_a_seed() {
+        let draw = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..10).map(|_| rng.range(5..=10)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(draw(1), draw(1));
+        assert_ne!(draw(1), draw(2));
+        assert!(draw(3).iter().all(|x| (5..=10).contains(x)));
+    }
+
+    #[test]
+    fn replays_choices() {
+        let mut rng = Rng::new(7);
+        let values = (0..5).map(|_| rng.below(100)).collect::<Vec<_>>();
+
+        let mut replay = Rng::replay(rng.into_choices());
+        assert_eq!(
+            (0..5).map(|_| replay.below(100)).collect::<Vec<_>>(),
+            values
+        );
+        assert_eq!(replay.range(3..=9), 3);
+        assert!(!replay.bool());
+        assert_eq!(replay.into_choices().len(), 7);
+    }
+}
@@ -4,49 +4,61 @@ use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    timings::{Machine, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    part: Option<u8>,
+) -> Result<Option<Timings>, Error> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if need_space {
+            println!();
+        }
+        need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = child_commands::run_solution(day, is_timed, is_release, part)?;
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+        if output.is_empty() {
+            println!("Not solved.");
+        } else {
+            let val = child_commands::parse_exec_time(&output, day);
+            timings.push(val);
+        }
+    }
 
     if is_timed {
+        let machine = Machine::detect(if is_release { "release" } else { "dev" });
+        for timing in &mut timings {
+            let parts = [timing.part_1.is_some(), timing.part_2.is_some()];
+            for (slot, timed) in timing.machines.iter_mut().zip(parts) {
+                if timed {
+                    *slot = Some(machine.clone());
+                }
+            }
+        }
+
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+        Ok(Some(timings))
     } else {
-        None
+        Ok(None)
     }
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
@@ -68,7 +80,11 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use crate::template::{
+        Day,
+        runner::{self, STACK_SIZE_HINT},
+        timings::parse_duration,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,26 +92,37 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day, optionally only running one part.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        part: Option<u8>,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
         let day_padded = day.to_string();
+        let part_str = part.map(|part| part.to_string());
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
         if is_release {
             args.push("--release");
         }
 
+        args.push("--");
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if let Some(part) = &part_str {
+            args.extend(["--part", part]);
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -111,18 +138,22 @@ pub mod child_commands {
         let mut output = vec![];
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                eprintln!("{line}");
+                if runner::is_stack_overflow(&line) {
+                    eprintln!("{STACK_SIZE_HINT}");
+                }
             });
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
+            let line = line?;
             println!("{line}");
             output.push(line);
         }
 
-        thread.join().unwrap();
+        // the thread only forwards output, so a panic there does not affect the result.
+        let _ = thread.join();
         cmd.wait()?;
 
         Ok(output)
@@ -134,6 +165,8 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            machines: [None, None],
+            implementations: [None, None],
         };
 
         output
@@ -149,13 +182,20 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                // parts with several implementations name the fastest one, e.g. `(1.2ms @ 100 samples) [part_one]`.
+                let implementation = l
+                    .rsplit_once(" samples) [")
+                    .and_then(|(_, name)| name.trim_end().strip_suffix(']'));
+                Some((part, timing_str, nanos, implementation))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, implementation)| {
+                let implementation = implementation.map(str::to_string);
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.implementations[0] = implementation;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.implementations[1] = implementation;
                 }
 
                 timings.total_nanos += nanos;
@@ -164,12 +204,7 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
@@ -179,18 +214,11 @@ pub mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        Some((str_timing, parse_duration(str_timing)?))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     macro_rules! assert_approx_eq {
         ($a:expr, $b:expr) => {{
             let (a, b) = (&$a, &$b);
@@ -203,7 +231,7 @@ pub mod child_commands {
         }};
     }
 
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     mod tests {
         use super::parse_exec_time;
 
@@ -239,6 +267,20 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_fastest_implementations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 10 (2ms @ 5 samples) [part_one]".into(),
+                    "Part 2: 10 (1ms @ 5 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), "2ms");
+            assert_eq!(res.implementations, [Some("part_one".to_string()), None]);
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -250,8 +292,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
         }
     }
 }
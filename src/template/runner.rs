@@ -1,35 +1,605 @@
 /// Encapsulates code that interacts with solution functions.
+use std::collections::HashMap;
+use std::error::Error;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{Write, stdout};
-use std::process::Output;
+use std::io::{self, Write, stdout};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, panic, process, thread};
+
+use tinyjson::JsonValue;
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::aoc_cli::AocCommandError;
+use crate::template::cli::{self, ArgsError, SOLUTION};
+use crate::template::{
+    ANSI_ITALIC, ANSI_RESET, Answer, Day, InputError, aoc_cli, config, normalize_input,
+    phases::{self, Phase},
+    read_path_raw, try_read_file, visualize,
+};
+
+/// How a solution binary prints its results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputMode {
+    /// Labelled answers and timings. `cargo all` and `cargo time` parse this format.
+    #[default]
+    Human,
+    /// Only the answers, one per line.
+    Quiet,
+    /// A single JSON document once all parts have run.
+    Json,
+}
+
+/// Command-line options of a solution binary, see [`SOLUTION`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    pub help: bool,
+    /// Only run this part.
+    pub part: Option<u8>,
+    pub time: bool,
+    pub submit: Option<u8>,
+    /// Read the input from this file instead of the inputs folder.
+    pub input: Option<PathBuf>,
+    pub output: OutputMode,
+    /// Show the frames of visualisations, see [`visualize`].
+    pub visualize: Option<visualize::Settings>,
+    /// Time the parts on generated inputs of growing size, see [`scale`](crate::template::scale).
+    pub scale: bool,
+}
+
+impl RunOptions {
+    /// Parses the options from the arguments the binary was called with.
+    pub fn from_env() -> Result<Self, ArgsError> {
+        Self::parse(
+            env::args_os()
+                .skip(1)
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+        )
+    }
+
+    pub fn parse(args: Vec<String>) -> Result<Self, ArgsError> {
+        cli::check_flags(Some(&SOLUTION), &args)?;
+
+        let invalid = |message: String| ArgsError::InvalidArgument {
+            command: SOLUTION.name,
+            message,
+        };
+
+        let mut args = pico_args::Arguments::from_vec(args.into_iter().map(Into::into).collect());
+        let mut part_value = |name: &'static str| -> Result<Option<u8>, ArgsError> {
+            match args.opt_value_from_str(name) {
+                Ok(Some(part @ (1 | 2))) => Ok(Some(part)),
+                Ok(Some(_)) => Err(invalid(format!("`{name}` expects 1 or 2"))),
+                Ok(None) => Ok(None),
+                Err(e) => Err(invalid(e.to_string())),
+            }
+        };
+
+        let part = part_value("--part")?;
+        let submit = part_value("--submit")?;
+        let input = args
+            .opt_value_from_str("--input")
+            .map_err(|e| invalid(e.to_string()))?;
+
+        let fps = args
+            .opt_value_from_str("--fps")
+            .map_err(|e| invalid(e.to_string()))?;
+        let frames = args
+            .opt_value_from_str("--frames")
+            .map_err(|e| invalid(e.to_string()))?;
+        // `--fps` and `--frames` imply `--visualize`.
+        let visualize = match (args.contains("--visualize"), fps, frames) {
+            (_, Some(0), _) => return Err(invalid("`--fps` must be at least 1".into())),
+            (false, None, None) => None,
+            (_, fps, frames) => Some(visualize::Settings {
+                fps: fps.unwrap_or(visualize::DEFAULT_FPS),
+                frames,
+            }),
+        };
+
+        let output = match (args.contains("--json"), args.contains("--quiet")) {
+            (true, true) => {
+                return Err(invalid("`--json` and `--quiet` cannot be combined".into()));
+            }
+            (true, false) => OutputMode::Json,
+            (false, true) => OutputMode::Quiet,
+            (false, false) => OutputMode::Human,
+        };
+
+        let options = Self {
+            help: args.contains(["-h", "--help"]),
+            part,
+            time: args.contains("--time"),
+            submit,
+            input,
+            output,
+            visualize,
+            scale: args.contains("--scale"),
+        };
+
+        if let Some(argument) = args.finish().first() {
+            return Err(ArgsError::UnexpectedArgument {
+                command: SOLUTION.name,
+                argument: argument.to_string_lossy().into_owned(),
+            });
+        }
+
+        if let (Some(part), Some(submit)) = (options.part, options.submit)
+            && part != submit
+        {
+            return Err(invalid(format!(
+                "cannot submit part {submit} when only running part {part}"
+            )));
+        }
+
+        if options.scale {
+            let conflicting = [
+                ("--time", options.time),
+                ("--submit", options.submit.is_some()),
+                ("--input", options.input.is_some()),
+                ("--json", options.output == OutputMode::Json),
+                ("--quiet", options.output == OutputMode::Quiet),
+                ("--visualize", options.visualize.is_some()),
+            ];
+            if let Some((flag, _)) = conflicting.iter().find(|(_, set)| *set) {
+                return Err(invalid(format!(
+                    "`--scale` cannot be combined with `{flag}`"
+                )));
+            }
+        }
+
+        if options.visualize.is_some() {
+            if options.time {
+                return Err(invalid(
+                    "visualisations do not run while benchmarking".into(),
+                ));
+            }
+            if options.output == OutputMode::Json {
+                return Err(invalid(
+                    "`--visualize` cannot be combined with `--json`".into(),
+                ));
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Returns the help text of a solution binary.
+    pub fn usage(day: Day) -> String {
+        SOLUTION.usage_for(&format!("cargo run --bin {day} --"))
+    }
+
+    /// Whether `part` should run.
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|x| x == part)
+    }
+
+    /// Reads the input from `--input` if set, or from the inputs folder otherwise.
+    pub fn read_input(&self, day: Day) -> Result<String, InputError> {
+        match &self.input {
+            Some(path) => read_path_raw(path).map(|s| normalize_input(&s)),
+            None => try_read_file("inputs", day),
+        }
+    }
+
+    /// Prints a message that is not a result to stdout, or to stderr if stdout is reserved for JSON.
+    fn print_message(&self, message: &str) {
+        if self.output == OutputMode::Json {
+            eprint!("{message}");
+        } else {
+            print!("{message}");
+        }
+    }
+}
+
+/// A named implementation of a part, see [`solution!`](crate::solution).
+pub type PartFn<'a, I, T> = (&'static str, &'a (dyn Fn(I) -> Option<T> + Sync));
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// The runtime of one implementation of a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImplementationResult {
+    pub name: &'static str,
+    /// Runtime of the implementation, averaged over `samples` runs when benchmarking.
+    pub duration: Duration,
+    pub samples: u128,
+    /// Time spent in each [`phase`](crate::template::phase) of the implementation, averaged like `duration`. Only recorded when benchmarking.
+    pub phases: Vec<Phase>,
+}
+
+/// The outcome of running one part.
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<Answer>,
+    /// All implementations of the part, in the order they were declared. They all found `answer`.
+    pub implementations: Vec<ImplementationResult>,
+    /// Index of the fastest implementation, whose runtime is the runtime of the part.
+    pub fastest: usize,
+}
+
+impl PartResult {
+    /// Returns the runtime of the fastest implementation.
+    pub fn fastest(&self) -> &ImplementationResult {
+        &self.implementations[self.fastest]
+    }
+}
+
+/// Prints `error` and exits the solution binary with a non-zero status.
+pub fn exit_with_error(error: impl Display) -> ! {
+    eprintln!("Error: {error}");
+    process::exit(1);
+}
+
+/// An error which can be returned when running a part.
+#[derive(Debug)]
+pub enum RunError {
+    /// The thread that runs the part could not be started, e.g. because its stack is too large.
+    Thread { stack_mb: u64, error: io::Error },
+    /// Submitting the answer failed.
+    Submit(AocCommandError),
+    /// Frames could not be shown or written.
+    Visualize(io::Error),
+    /// `--scale` was passed, but the day has no input generator.
+    NoGenerator(Day),
+    /// Two implementations of a part found different answers.
+    Mismatch {
+        part: u8,
+        expected: (&'static str, Option<Answer>),
+        actual: (&'static str, Option<Answer>),
+    },
+}
+
+impl Error for RunError {}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::Thread { stack_mb, error } => write!(
+                f,
+                "could not start a thread with a stack of {stack_mb} MiB: {error}. {STACK_SIZE_HINT}"
+            ),
+            RunError::Submit(e) => write!(f, "{e}"),
+            RunError::Visualize(e) => write!(f, "could not show frames: {e}"),
+            RunError::NoGenerator(day) => write!(
+                f,
+                "day {day} has no input generator to scale with. Pass one to `solution!`, e.g. `solution!({}; generator = generate)`.",
+                day.into_inner()
+            ),
+            RunError::Mismatch {
+                part,
+                expected,
+                actual,
+            } => {
+                let format = |answer: &Option<Answer>| {
+                    answer.as_ref().map_or("no answer".into(), |answer| {
+                        answer.text().unwrap_or_else(|| answer.to_string())
+                    })
+                };
+                write!(
+                    f,
+                    "implementations of part {part} disagree: `{}` found {}, `{}` found {}.",
+                    expected.0,
+                    format(&expected.1),
+                    actual.0,
+                    format(&actual.1)
+                )
+            }
+        }
+    }
+}
+
+impl From<AocCommandError> for RunError {
+    fn from(e: AocCommandError) -> Self {
+        RunError::Submit(e)
+    }
+}
+
+/// Where stack sizes are configured, for errors and stack overflows.
+pub(crate) const STACK_SIZE_HINT: &str =
+    "The stack size of parts is set by `stack.size_mb` or `stack.day_NN` in aoc.toml.";
+
+/// Whether a line of a solution's stderr reports a stack overflow, e.g. "thread 'part 1' has overflowed its stack".
+pub(crate) fn is_stack_overflow(line: &str) -> bool {
+    line.contains(" has overflowed its stack")
+}
+
+/// Whether a solution exited the way it does after a stack overflow, for callers that do not read its stderr:
+/// killed by `SIGABRT` or `SIGSEGV` on unix, or with `STATUS_STACK_OVERFLOW` on windows.
+pub(crate) fn may_have_overflowed(status: process::ExitStatus) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        matches!(status.signal(), Some(6 | 11))
+    }
+    #[cfg(windows)]
+    {
+        status.code() == Some(0xC000_00FD_u32 as i32)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = status;
+        false
+    }
+}
+
+/// Runs a part, prints its result and submits it if requested.
+///
+/// A part can have several implementations, which all have to find the same answer.
+/// The first one is the reference: it is the only one that shows visualisations or is profiled with `dhat`.
+/// When benchmarking, each implementation is benchmarked and the fastest one is reported as the runtime of the part.
+///
+/// Parts run on their own thread with the stack size configured for the day, see [`config::Stack`].
+/// A stack overflow aborts the process with a message that names the thread, e.g. `part 1`.
+///
+/// # Panics
+/// Panics if `implementations` is empty, or if one of them panics.
+pub fn run_part<I: Copy + Send, T: Into<Answer>>(
+    implementations: &[PartFn<I, T>],
+    input: I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> Result<PartResult, RunError> {
+    assert!(
+        !implementations.is_empty(),
+        "part {part} has no implementation"
+    );
+
+    on_part_thread(day, part, move || {
+        run_part_on_thread(implementations, input, day, part, options)
+    })
+}
+
+/// Runs `func` on a thread named after the part, with the stack size configured for the day.
+pub(crate) fn on_part_thread<R: Send>(
+    day: Day,
+    part: u8,
+    func: impl FnOnce() -> Result<R, RunError> + Send,
+) -> Result<R, RunError> {
+    let stack_mb = config::get().stack.size_mb_for(day);
+    let stack_size = usize::try_from(stack_mb << 20).unwrap_or(usize::MAX);
+
+    thread::scope(|scope| {
+        let handle = thread::Builder::new()
+            .name(format!("part {part}"))
+            .stack_size(stack_size)
+            .spawn_scoped(scope, func)
+            .map_err(|error| RunError::Thread { stack_mb, error })?;
+
+        // panics have already been reported by the part's thread, keep failing the same way.
+        handle
+            .join()
+            .unwrap_or_else(|payload| panic::resume_unwind(payload))
+    })
+}
+
+fn run_part_on_thread<I: Copy, T: Into<Answer>>(
+    implementations: &[PartFn<I, T>],
+    input: I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> Result<PartResult, RunError> {
     let part_str = format!("Part {part}");
+    let is_human = options.output == OutputMode::Human;
+    let (name, func) = implementations[0];
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    if let Some(settings) = &options.visualize {
+        visualize::start(settings, part).map_err(RunError::Visualize)?;
+    }
+    let mut frames = None;
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let (answer, duration, samples, phases) = run_timed(func, input, options, |result| {
+        // frames are only shown for the first run, never while benchmarking.
+        if options.visualize.is_some() {
+            frames = Some(visualize::stop());
+        }
+        let answer = result.map(Into::into);
+        if is_human {
+            print_result(&answer, &part_str, "");
+        }
+        answer
+    });
+    let mut results = vec![ImplementationResult {
+        name,
+        duration,
+        samples,
+        phases,
+    }];
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    // a heap profile only covers a single implementation.
+    let alternatives = if cfg!(feature = "dhat-heap") {
+        &[]
+    } else {
+        &implementations[1..]
+    };
+
+    for &(other_name, other) in alternatives {
+        let (other_answer, duration, samples, phases) =
+            run_timed(other, input, options, |result| result.map(Into::into));
+        if other_answer != answer {
+            return Err(RunError::Mismatch {
+                part,
+                expected: (name, answer),
+                actual: (other_name, other_answer),
+            });
+        }
+
+        results.push(ImplementationResult {
+            name: other_name,
+            duration,
+            samples,
+            phases,
+        });
+    }
+
+    let fastest = (0..results.len())
+        .min_by_key(|i| results[*i].duration)
+        .unwrap_or_default();
+
+    match options.output {
+        OutputMode::Human => {
+            let result = &results[fastest];
+            let mut duration_str = format_duration(&result.duration, result.samples);
+            if results.len() > 1 {
+                duration_str.push_str(&format!(" [{}]", result.name));
+            }
+            print_result(&answer, &part_str, &duration_str);
+            print_phases(&result.phases, &result.duration);
+            print_implementations(&results, fastest);
+        }
+        OutputMode::Quiet => {
+            if let Some(answer) = &answer {
+                println!("{}", answer.text().unwrap_or_else(|| answer.to_string()));
+            }
+        }
+        OutputMode::Json => {}
+    }
+
+    if let Some(frames) = frames {
+        let count = frames.map_err(RunError::Visualize)?;
+        if let Some(path) = options.visualize.as_ref().and_then(|x| x.frames.as_ref()) {
+            options.print_message(&format!(
+                "Wrote {count} frame(s) of part {part} to \"{}\".\n",
+                path.display()
+            ));
+        }
+    }
+
+    if let Some(answer) = &answer
+        && options.submit == Some(part)
+    {
+        aoc_cli::check()?;
+
+        options.print_message("Submitting result via aoc-cli...\n");
+        let submission = aoc_cli::submit(day, part, answer);
+
+        match &submission {
+            Ok(output) | Err(AocCommandError::BadExitStatus(output)) => {
+                options.print_message(&String::from_utf8_lossy(&output.stdout));
+            }
+            Err(e @ AocCommandError::RejectedAnswer(_)) => options.print_message(&format!("{e}\n")),
+            Err(_) => {}
+        }
+
+        // part two's description is only served once part one is solved.
+        if part == 1 && submission.is_ok_and(|x| aoc_cli::is_part_solved(&x)) {
+            options.print_message("Refreshing puzzle description via aoc-cli...\n");
+            if let Err(e) = aoc_cli::download_puzzle(day) {
+                eprintln!("failed to refresh puzzle description: {e}");
+            }
+        }
+    }
+
+    Ok(PartResult {
+        part,
+        answer,
+        implementations: results,
+        fastest,
+    })
+}
+
+/// Prints the results of all parts that ran. Only `--json` prints anything here, other modes print each part as it completes.
+pub fn print_results(day: Day, options: &RunOptions, results: &[PartResult]) {
+    if options.output != OutputMode::Json {
+        return;
+    }
+
+    let parts = results
+        .iter()
+        .map(|result| {
+            let fastest = result.fastest();
+            let mut json = implementation_json(fastest);
+            json.extend([
+                ("part".into(), JsonValue::Number(f64::from(result.part))),
+                (
+                    "answer".into(),
+                    result.answer.as_ref().map_or(JsonValue::Null, |answer| {
+                        JsonValue::String(answer.text().unwrap_or_else(|| answer.to_string()))
+                    }),
+                ),
+                (
+                    "type".into(),
+                    result.answer.as_ref().map_or(JsonValue::Null, |answer| {
+                        JsonValue::String(answer.kind().into())
+                    }),
+                ),
+                (
+                    "implementations".into(),
+                    JsonValue::Array(
+                        result
+                            .implementations
+                            .iter()
+                            .map(|x| JsonValue::Object(implementation_json(x)))
+                            .collect(),
+                    ),
+                ),
+            ]);
+            JsonValue::Object(json)
+        })
+        .collect();
+
+    let json = JsonValue::Object(HashMap::from([
+        ("day".into(), JsonValue::Number(f64::from(day.into_inner()))),
+        ("parts".into(), JsonValue::Array(parts)),
+    ]));
+
+    match json.stringify() {
+        Ok(json) => println!("{json}"),
+        Err(e) => eprintln!("Could not serialize results: {e}"),
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
+/// Serializes the runtime of an implementation. The fields are also used for the runtime of a part.
+fn implementation_json(result: &ImplementationResult) -> HashMap<String, JsonValue> {
+    HashMap::from([
+        ("name".into(), JsonValue::String(result.name.into())),
+        (
+            "nanos".into(),
+            #[allow(clippy::cast_precision_loss)]
+            JsonValue::Number(result.duration.as_nanos() as f64),
+        ),
+        (
+            "samples".into(),
+            #[allow(clippy::cast_precision_loss)]
+            JsonValue::Number(result.samples as f64),
+        ),
+        (
+            "phases".into(),
+            JsonValue::Array(
+                result
+                    .phases
+                    .iter()
+                    .map(|phase| {
+                        JsonValue::Object(HashMap::from([
+                            ("name".into(), JsonValue::String(phase.name.into())),
+                            (
+                                "nanos".into(),
+                                #[allow(clippy::cast_precision_loss)]
+                                JsonValue::Number(phase.duration.as_nanos() as f64),
+                            ),
+                        ]))
+                    })
+                    .collect(),
+            ),
+        ),
+    ])
+}
+
+/// Run a solution part. The behavior differs depending on whether `--time` is set:
+///  1. without it, the function is executed once.
+///  2. with it, the function is benched (approx. `bench.budget_ms` of execution time or `bench.min_samples` samples, whatever take longer.)
+///
+/// The result is passed to `hook` before benching starts, the value it returns is returned along with the timing
+/// and the time per run of each phase, which are only measured when benching.
+fn run_timed<I: Copy, T, R>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    options: &RunOptions,
+    hook: impl FnOnce(T) -> R,
+) -> (R, Duration, u128, Vec<Phase>) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -39,25 +609,35 @@ fn run_timed<I: Copy, T>(
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let result = hook(result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let (duration, samples, phases) = if options.time {
+        bench(func, input, &base_time, options.output == OutputMode::Human)
     } else {
-        (base_time, 1)
+        (base_time, 1, vec![])
     };
 
-    (result, run.0, run.1)
+    (result, duration, samples, phases)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    show_progress: bool,
+) -> (Duration, u128, Vec<Phase>) {
+    if show_progress {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let settings = &config::get().bench;
+    let bench_iterations = (Duration::from_millis(settings.budget_ms).as_nanos()
+        / cmp::max(base_time.as_nanos(), 10))
+    .clamp(
+        u128::from(settings.min_samples),
+        u128::from(settings.max_samples),
+    );
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,9 +651,26 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         #[allow(clippy::cast_possible_truncation)]
         Duration::from_nanos(average_duration(&timers) as u64),
         bench_iterations,
+        bench_phases(&func, input, bench_iterations),
     )
 }
 
+/// Records the phases of `func` in runs of their own, as recording them slows the runs down.
+/// Parts without phases are only run once more, otherwise the phases are averaged over `iterations` runs.
+fn bench_phases<I: Copy, T>(func: impl Fn(I) -> T, input: I, iterations: u128) -> Vec<Phase> {
+    phases::start();
+    black_box(func(black_box(input)));
+    if phases::finish().is_empty() {
+        return vec![];
+    }
+
+    phases::start();
+    for _ in 0..iterations {
+        black_box(func(black_box(input)));
+    }
+    average_phases(phases::finish(), iterations)
+}
+
 fn average_duration(numbers: &[Duration]) -> u128 {
     numbers
         .iter()
@@ -82,6 +679,55 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
+/// Turns the total time of each phase over `samples` runs into the time per run.
+fn average_phases(mut phases: Vec<Phase>, samples: u128) -> Vec<Phase> {
+    for phase in &mut phases {
+        #[allow(clippy::cast_possible_truncation)]
+        let nanos = (phase.duration.as_nanos() / cmp::max(samples, 1)) as u64;
+        phase.duration = Duration::from_nanos(nanos);
+    }
+    phases
+}
+
+/// Prints the runtime of each implementation of a part that has several, compared to the fastest one.
+fn print_implementations(results: &[ImplementationResult], fastest: usize) {
+    if results.len() < 2 {
+        return;
+    }
+
+    let width = results.iter().map(|x| x.name.len()).max().unwrap_or(0);
+    let best = cmp::max(results[fastest].duration.as_nanos(), 1);
+    for (i, result) in results.iter().enumerate() {
+        let duration = format!("{:.1?}", result.duration);
+        if i == fastest {
+            println!("  ★ {:width$} {duration:>10}", result.name);
+        } else {
+            #[allow(clippy::cast_precision_loss)]
+            let ratio = result.duration.as_nanos() as f64 / best as f64;
+            println!("    {:width$} {duration:>10} ({ratio:.2}x)", result.name);
+        }
+    }
+}
+
+/// Prints the time spent in each phase, below the line of its part.
+fn print_phases(phases: &[Phase], total: &Duration) {
+    let width = phases
+        .iter()
+        .map(|phase| phase.name.len())
+        .max()
+        .unwrap_or(0);
+    for phase in phases {
+        #[allow(clippy::cast_precision_loss)]
+        let share = phase.duration.as_nanos() as f64 / cmp::max(total.as_nanos(), 1) as f64;
+        println!(
+            "  ↳ {:width$} {:>10} {:>5.1}%",
+            phase.name,
+            format!("{:.1?}", phase.duration),
+            share * 100_f64
+        );
+    }
+}
+
 fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
@@ -90,28 +736,31 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Option<Answer>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
+        Some(answer @ Answer::Grid(rows)) => {
+            // grids that draw letters are labelled with the text they spell.
+            let str = match answer.text() {
+                Some(text) => format!("{part}: {ANSI_BOLD}{text}{ANSI_RESET}{duration_str}"),
+                None => format!("{part}: ▼ {duration_str}"),
+            };
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}");
+                println!("{}", rows.join("\n"));
+            }
+        }
+        Some(answer) => {
+            let str = format!("{part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration_str}");
+            if is_intermediate_result {
+                print!("{str}");
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
+                print!("\r");
+                println!("{str}");
             }
         }
         None => {
@@ -125,43 +774,77 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/* -------------------------------------------------------------------------- */
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{OutputMode, RunOptions};
+    use crate::template::cli::ArgsError;
+
+    fn parse(args: &[&str]) -> Result<RunOptions, ArgsError> {
+        RunOptions::parse(args.iter().map(|x| (*x).to_string()).collect())
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    #[test]
+    fn parses_solution_arguments() {
+        let options = parse(&["--part", "2", "--time", "--input", "a.txt", "--quiet"]).unwrap();
+        assert_eq!(options.part, Some(2));
+        assert!(options.time);
+        assert_eq!(
+            options.input.as_deref().and_then(|x| x.to_str()),
+            Some("a.txt")
+        );
+        assert_eq!(options.output, OutputMode::Quiet);
+        assert!(!options.runs_part(1) && parse(&[]).unwrap().runs_part(1));
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    #[test]
+    fn parses_visualize_arguments() {
+        let options = parse(&["--visualize"]).unwrap();
+        assert_eq!(options.visualize.map(|x| x.fps), Some(10));
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+        let options = parse(&["--frames", "frames.txt", "--fps", "30"]).unwrap();
+        let settings = options.visualize.unwrap();
+        assert_eq!(settings.fps, 30);
+        assert_eq!(
+            settings.frames.as_deref().and_then(|x| x.to_str()),
+            Some("frames.txt")
+        );
 
-    if part_submit != part {
-        return None;
+        assert_eq!(parse(&[]).unwrap().visualize, None);
+        assert!(parse(&["--visualize", "--time"]).is_err());
+        assert!(parse(&["--visualize", "--json"]).is_err());
+        assert!(parse(&["--fps", "0"]).is_err());
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
+    #[test]
+    fn parses_scale_arguments() {
+        let options = parse(&["--scale", "--part", "2"]).unwrap();
+        assert!(options.scale);
+        assert_eq!(options.part, Some(2));
+
+        assert!(!parse(&[]).unwrap().scale);
+        assert!(parse(&["--scale", "--time"]).is_err());
+        assert!(parse(&["--scale", "--input", "input.txt"]).is_err());
+        assert!(parse(&["--scale", "--json"]).is_err());
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn rejects_invalid_solution_arguments() {
+        assert!(matches!(
+            parse(&["--tiem"]),
+            Err(ArgsError::UnknownFlag {
+                suggestion: Some("--time"),
+                ..
+            })
+        ));
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--submit"]).is_err());
+        assert!(parse(&["--json", "--quiet"]).is_err());
+        assert!(parse(&["--part", "1", "--submit", "2"]).is_err());
+        assert!(matches!(
+            parse(&["input.txt"]),
+            Err(ArgsError::UnexpectedArgument { .. })
+        ));
+    }
 }
@@ -0,0 +1,156 @@
+/// Scaling analysis: times each part on generated inputs of growing size and estimates its complexity exponent.
+/// Days opt in by passing an input [`Generator`] to [`solution!`](crate::solution).
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Rng,
+    runner::{RunError, RunOptions, on_part_thread},
+};
+
+/// Builds an input of the given size, e.g. with that many lines.
+/// Generators are also used for [`Differential`](crate::template::property::Differential) tests.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// A part and the implementation that is timed.
+pub type ScalePart<'a> = (u8, &'a (dyn Fn(&str) + Sync));
+
+const FIRST_SIZE: usize = 16;
+const MAX_SIZE: usize = 1 << 20;
+/// Sizes stop growing once a single run takes longer than this.
+const MAX_RUN: Duration = Duration::from_secs(1);
+/// Each size is run repeatedly for this long, and the fastest run is kept.
+const SAMPLE_BUDGET: Duration = Duration::from_millis(200);
+/// The exponent is fitted on the larger half of the sizes, but at least on this many.
+const MIN_FIT_POINTS: usize = 3;
+
+/// Times the parts on inputs of doubling size, and prints the runtime per size and the estimated exponent.
+pub fn run_scale(
+    day: Day,
+    generator: Option<Generator>,
+    parts: &[ScalePart],
+    options: &RunOptions,
+) -> Result<(), RunError> {
+    let generator = generator.ok_or(RunError::NoGenerator(day))?;
+
+    for &(part, func) in parts.iter().filter(|(part, _)| options.runs_part(*part)) {
+        println!("{ANSI_BOLD}Part {part}{ANSI_RESET} {ANSI_ITALIC}(generated inputs){ANSI_RESET}");
+        println!("{:>10} {:>12}", "size", "time");
+
+        let points = on_part_thread(day, part, move || Ok(measure(generator, func)))?;
+
+        match fit_exponent(&points) {
+            Some(exponent) => println!(
+                "  ≈ O(n^{exponent:.2}){}",
+                describe(exponent).map_or(String::new(), |name| format!(", roughly {name}"))
+            ),
+            None => println!("  not enough sizes ran to estimate the complexity."),
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Times `func` on inputs of doubling size, printing each size as it finishes.
+fn measure(generator: Generator, func: &dyn Fn(&str)) -> Vec<(usize, Duration)> {
+    let mut points = vec![];
+    let mut size = FIRST_SIZE;
+
+    while size <= MAX_SIZE {
+        let input = generator(&mut Rng::new(size as u64), size);
+
+        let start = Instant::now();
+        let mut fastest = Duration::MAX;
+        while start.elapsed() < SAMPLE_BUDGET {
+            let timer = Instant::now();
+            func(black_box(&input));
+            fastest = fastest.min(timer.elapsed());
+            if fastest > MAX_RUN {
+                break;
+            }
+        }
+
+        println!("{size:>10} {:>12}", format!("{fastest:.1?}"));
+        points.push((size, fastest));
+        if fastest > MAX_RUN {
+            break;
+        }
+        size *= 2;
+    }
+
+    points
+}
+
+/// Fits `time = c * size^k` to the larger sizes by least squares on a log-log scale and returns `k`.
+/// Small sizes are left out, as their runtime is dominated by constant overhead.
+fn fit_exponent(points: &[(usize, Duration)]) -> Option<f64> {
+    if points.len() < MIN_FIT_POINTS {
+        return None;
+    }
+
+    let skip = (points.len() / 2).min(points.len() - MIN_FIT_POINTS);
+    #[allow(clippy::cast_precision_loss)]
+    let logs: Vec<(f64, f64)> = points[skip..]
+        .iter()
+        .map(|(size, time)| ((*size as f64).ln(), (time.as_nanos().max(1) as f64).ln()))
+        .collect();
+
+    #[allow(clippy::cast_precision_loss)]
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/// Names exponents that are close to a whole number. Logarithmic factors, e.g. of sorting, and caches
+/// make measured exponents a bit larger than the whole number, e.g. `2.2` for `n² log n`.
+fn describe(exponent: f64) -> Option<&'static str> {
+    const NAMES: [&str; 4] = ["constant", "linear", "quadratic", "cubic"];
+
+    let rounded = exponent.round();
+    if (exponent - rounded).abs() > 0.4 || !(0.0..=3.0).contains(&rounded) {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    NAMES.get(rounded as usize).copied()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{describe, fit_exponent};
+
+    #[test]
+    fn fits_exponents() {
+        let points = |exponent: u32| {
+            (4..12)
+                .map(|i| {
+                    let size = 1_usize << i;
+                    // constant overhead that only matters for small sizes.
+                    let nanos = 5_000 + (size as u64).pow(exponent);
+                    (size, Duration::from_nanos(nanos))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let quadratic = fit_exponent(&points(2)).unwrap();
+        assert!((quadratic - 2.0).abs() < 0.1, "{quadratic}");
+        assert_eq!(describe(quadratic), Some("quadratic"));
+
+        let cubic = fit_exponent(&points(3)).unwrap();
+        assert!((cubic - 3.0).abs() < 0.1, "{cubic}");
+
+        assert_eq!(fit_exponent(&points(1)[..2]), None);
+        assert_eq!(describe(1.15), Some("linear"));
+        assert_eq!(describe(1.5), None);
+    }
+}
@@ -0,0 +1,182 @@
+/// Bookkeeping of wrong answers that were submitted, so that answers which are known to be wrong are not submitted again.
+/// Wrong answers are stored per day in `data/submissions/NN.txt`, one `<part> <verdict> <answer>` line each.
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::template::{Answer, Day, config};
+
+/// Why a submitted answer was wrong.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl Verdict {
+    /// Reads the verdict from the response to a submission. Returns `None` unless the answer was wrong.
+    pub fn parse(response: &str) -> Option<Self> {
+        if !response.contains("That's not the right answer") {
+            return None;
+        }
+
+        Some(if response.contains("too high") {
+            Verdict::TooHigh
+        } else if response.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        })
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "too_high" => Some(Verdict::TooHigh),
+            "too_low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+/// The wrong answers that were submitted for a day.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    entries: Vec<(u8, Verdict, String)>,
+}
+
+impl Submissions {
+    fn path(day: Day) -> PathBuf {
+        config::get()
+            .paths
+            .data
+            .join("submissions")
+            .join(format!("{day}.txt"))
+    }
+
+    /// Reads the wrong answers of a day. Returns no answers if none have been recorded yet.
+    pub fn read(day: Day) -> Self {
+        fs::read_to_string(Self::path(day))
+            .map(|s| Self::parse(&s))
+            .unwrap_or_default()
+    }
+
+    /// Parses the stored format, skipping lines that are not understood.
+    pub fn parse(s: &str) -> Self {
+        let entries = s
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, ' ');
+                let part = fields.next()?.parse().ok()?;
+                let verdict = Verdict::from_key(fields.next()?)?;
+                Some((part, verdict, fields.next()?.to_string()))
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Appends a wrong answer to the file of a day.
+    pub fn record(day: Day, part: u8, verdict: Verdict, answer: &str) -> io::Result<()> {
+        let path = Self::path(day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{part} {} {answer}", verdict.key())
+    }
+
+    /// Returns why `answer` is known to be wrong, based on earlier submissions of the same part.
+    /// `text` is the answer as it would be submitted.
+    pub fn check(&self, part: u8, answer: &Answer, text: &str) -> Result<(), String> {
+        for (_, verdict, previous) in self.entries.iter().filter(|(p, _, _)| *p == part) {
+            if previous == text {
+                return Err(format!("{text} was submitted before and is {verdict}"));
+            }
+
+            let (Some(number), Ok(previous_number)) = (answer.number(), previous.parse::<i128>())
+            else {
+                continue;
+            };
+
+            match verdict {
+                Verdict::TooHigh if number >= previous_number => {
+                    return Err(format!("{text} is too high, {previous} already was"));
+                }
+                Verdict::TooLow if number <= previous_number => {
+                    return Err(format!("{text} is too low, {previous} already was"));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Submissions, Verdict};
+    use crate::template::Answer;
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::parse(
+                "That's not the right answer; your answer is too high. If you're stuck, ..."
+            ),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too low."),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer. If you're stuck, ..."),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(Verdict::parse("That's the right answer!"), None);
+        assert_eq!(Verdict::parse("You gave an answer too recently"), None);
+    }
+
+    #[test]
+    fn rejects_known_wrong_answers() {
+        let submissions =
+            Submissions::parse("1 too_high 100\n1 too_low 10\n1 wrong 50\n2 wrong ABC\ninvalid\n");
+        let check = |part, answer: Answer| submissions.check(part, &answer, &answer.to_string());
+
+        assert!(check(1, Answer::Unsigned(100)).is_err());
+        assert!(check(1, Answer::Unsigned(120)).is_err());
+        assert!(check(1, Answer::Unsigned(10)).is_err());
+        assert!(check(1, Answer::Signed(5)).is_err());
+        assert!(check(1, Answer::Unsigned(50)).is_err());
+        assert!(check(1, Answer::Unsigned(42)).is_ok());
+        assert!(check(2, Answer::Text("ABC".into())).is_err());
+        assert!(check(2, Answer::Unsigned(100)).is_ok());
+    }
+}
@@ -1,9 +1,96 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    env, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::Error,
+    path::Path,
+    process::Command,
+    str::FromStr,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{Day, config};
+
+/// The environment a benchmark was run in, so that timings from different machines can be told apart.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Machine {
+    /// A salted hash of `/etc/machine-id`, or of the hostname where that is not available.
+    pub id: String,
+    /// Model name from `/proc/cpuinfo`.
+    pub cpu: Option<String>,
+    /// Output of `rustc --version`.
+    pub rustc: Option<String>,
+    /// Cargo profile the solutions were built with.
+    pub profile: String,
+}
+
+impl Machine {
+    pub fn detect(profile: &str) -> Self {
+        let read_trimmed = |path: &str| {
+            fs::read_to_string(path)
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+
+        let id = read_trimmed("/etc/machine-id")
+            .or_else(|| env::var("HOSTNAME").ok())
+            .or_else(|| env::var("COMPUTERNAME").ok())
+            .or_else(|| read_trimmed("/etc/hostname"))
+            .map_or_else(|| "unknown".into(), |id| app_specific_id(&id));
+
+        let cpu = fs::read_to_string("/proc/cpuinfo")
+            .ok()
+            .and_then(|s| parse_cpu_model(&s));
+
+        let rustc = Command::new("rustc")
+            .arg("--version")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+        Machine {
+            id,
+            cpu,
+            rustc,
+            profile: profile.into(),
+        }
+    }
+
+    /// Formats the machine as a single line, with the ID shortened to 8 characters.
+    pub fn summary(&self) -> String {
+        [
+            Some(self.id.chars().take(8).collect()),
+            self.cpu.clone(),
+            self.rustc.clone(),
+            Some(self.profile.clone()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ")
+    }
+}
+
+/// Reads the CPU model from the contents of `/proc/cpuinfo`. ARM systems report it as `Model` instead of `model name`.
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    ["model name", "Model"].iter().find_map(|key| {
+        cpuinfo.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == *key && !value.trim().is_empty()).then(|| value.trim().to_string())
+        })
+    })
+}
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+/// The machine ID is confidential (see `machine-id(5)`), so timings store a hash of it with a fixed salt instead.
+/// `DefaultHasher::new` always uses the same keys, so the hash is the same on every run.
+fn app_specific_id(machine_id: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    "advent_of_code".hash(&mut hasher);
+    machine_id.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -12,6 +99,47 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// The machines each part was measured on.
+    /// Missing in timings that were stored before machines were recorded.
+    pub machines: [Option<Machine>; 2],
+    /// Names of the fastest implementations of parts that have several.
+    pub implementations: [Option<String>; 2],
+}
+
+impl Timing {
+    /// Returns a copy of `self` with the timing of `part` taken from `other`.
+    fn with_part(&self, other: &Timing, part: u8) -> Timing {
+        let mut timing = self.clone();
+        let index = usize::from(part - 1);
+        timing.implementations[index].clone_from(&other.implementations[index]);
+        timing.machines[index].clone_from(&other.machines[index]);
+        let (old, new) = if part == 1 {
+            (&mut timing.part_1, &other.part_1)
+        } else {
+            (&mut timing.part_2, &other.part_2)
+        };
+
+        let old_nanos = old.as_deref().and_then(parse_duration).unwrap_or_default();
+        let new_nanos = new.as_deref().and_then(parse_duration).unwrap_or_default();
+        timing.total_nanos = (timing.total_nanos - old_nanos).max(0_f64) + new_nanos;
+        old.clone_from(new);
+        timing
+    }
+}
+
+/// Parses a duration as formatted by [`std::time::Duration`]'s `Debug`, e.g. `74.13µs`, to nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+        s.split(postfix).next()?.parse().ok()
+    }
+
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -25,24 +153,33 @@ impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(&config::get().paths.timings)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        Self::read(&config::get().paths.timings).unwrap_or_default()
+    }
+
+    /// Rehydrate timings from the JSON file at `path`.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        fs::read_to_string(path)
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
-            .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
-    pub fn merge(&self, new: &Self) -> Self {
+    /// If `part` is set, `new` only contains timings for that part and the other part of existing days is kept.
+    pub fn merge(&self, new: &Self, part: Option<u8>) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let existing = self.data.iter().find(|t| t.day == timing.day);
+            data.push(match (part, existing) {
+                (Some(part), Some(existing)) => existing.with_part(timing, part),
+                _ => timing.clone(),
+            });
         }
 
         for timing in &self.data {
@@ -51,7 +188,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -133,6 +270,24 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, name) in ["part_1_implementation", "part_2_implementation"]
+            .into_iter()
+            .zip(&value.implementations)
+        {
+            if let Some(name) = name {
+                map.insert(key.into(), JsonValue::String(name.clone()));
+            }
+        }
+
+        for (key, machine) in ["part_1_machine", "part_2_machine"]
+            .into_iter()
+            .zip(&value.machines)
+        {
+            if let Some(machine) = machine {
+                map.insert(key.into(), JsonValue::from(machine));
+            }
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,18 +321,64 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let machine = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => Machine::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
+        let implementation = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            machines: [machine("part_1_machine")?, machine("part_2_machine")?],
+            implementations: [
+                implementation("part_1_implementation"),
+                implementation("part_2_implementation"),
+            ],
         })
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+impl From<&Machine> for JsonValue {
+    fn from(value: &Machine) -> Self {
+        let optional = |x: &Option<String>| x.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        JsonValue::Object(HashMap::from([
+            ("id".into(), JsonValue::String(value.id.clone())),
+            ("cpu".into(), optional(&value.cpu)),
+            ("rustc".into(), optional(&value.rustc)),
+            ("profile".into(), JsonValue::String(value.profile.clone())),
+        ]))
+    }
+}
+
+impl TryFrom<&JsonValue> for Machine {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected the machine of a timing to be a JSON object.")?;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        Ok(Machine {
+            id: string("id").ok_or("Expected the machine id to be a string.")?,
+            cpu: string("cpu"),
+            rustc: string("rustc"),
+            profile: string("profile").ok_or("Expected the machine profile to be a string.")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
     use crate::day;
 
@@ -191,18 +392,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    machines: [None, None],
+                    implementations: [None, None],
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    machines: [None, None],
+                    implementations: [None, None],
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    machines: [None, None],
+                    implementations: [None, None],
                 },
             ],
         }
@@ -253,7 +460,7 @@ mod tests {
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
-            let value = JsonValue::try_from(timings).unwrap();
+            let value = JsonValue::from(timings);
             assert_eq!(
                 value
                     .get::<HashMap<String, JsonValue>>()
@@ -282,10 +489,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    machines: [None, None],
+                    implementations: [None, None],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert!(timings.is_day_complete(day!(1)));
         }
 
         #[test]
@@ -296,10 +505,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    machines: [None, None],
+                    implementations: [None, None],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
 
         #[test]
@@ -310,17 +521,19 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    machines: [None, None],
+                    implementations: [None, None],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{Machine, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -334,9 +547,11 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    machines: [None, None],
+                    implementations: [None, None],
                 }],
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, None);
             assert_eq!(merged.data.len(), 4);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
@@ -354,9 +569,11 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    machines: [None, None],
+                    implementations: [None, None],
                 }],
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, None);
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
@@ -369,7 +586,7 @@ mod tests {
         fn handles_empty_timings() {
             let timings = Timings::default();
             let other = get_mock_timings();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, None);
             assert_eq!(merged.data.len(), 3);
         }
 
@@ -377,8 +594,108 @@ mod tests {
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
             let other = Timings::default();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, None);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn merges_single_parts() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![
+                    Timing {
+                        day: day!(2),
+                        part_1: None,
+                        part_2: Some("10ms".into()),
+                        total_nanos: 1e+7,
+                        machines: [None, None],
+                        implementations: [None, None],
+                    },
+                    Timing {
+                        day: day!(3),
+                        part_1: None,
+                        part_2: Some("1ms".into()),
+                        total_nanos: 1e+6,
+                        machines: [None, None],
+                        implementations: [None, None],
+                    },
+                ],
+            };
+            let merged = timings.merge(&other, Some(2));
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[1].part_1.as_deref(), Some("30ms"));
+            assert_eq!(merged.data[1].part_2.as_deref(), Some("10ms"));
+            assert_eq!(merged.data[1].total_nanos, 6.997e+10);
+            assert_eq!(merged.data[2].part_1, None);
+            assert_eq!(merged.data[2].part_2.as_deref(), Some("1ms"));
+        }
+
+        #[test]
+        fn keeps_the_machine_of_the_other_part() {
+            let machine = |id: &str| Machine {
+                id: id.into(),
+                cpu: None,
+                rustc: None,
+                profile: "release".into(),
+            };
+            let mut timings = get_mock_timings();
+            timings.data[0].machines = [Some(machine("a")), Some(machine("a"))];
+            let mut other = get_mock_timings();
+            other.data.truncate(1);
+            other.data[0].machines = [None, Some(machine("b"))];
+
+            let merged = timings.merge(&other, Some(2));
+            assert_eq!(
+                merged.data[0].machines,
+                [Some(machine("a")), Some(machine("b"))]
+            );
+        }
+    }
+
+    mod machine {
+        use crate::template::timings::{Machine, Timings, app_specific_id, parse_cpu_model};
+
+        #[test]
+        fn derives_machine_ids() {
+            let machine_id = "0123456789abcdef0123456789abcdef";
+            let id = app_specific_id(machine_id);
+            assert_eq!(id.len(), 16);
+            assert_ne!(id, machine_id);
+            assert_eq!(id, app_specific_id(machine_id));
+        }
+
+        #[test]
+        fn parses_cpu_models() {
+            let x86 = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel\t\t: 33\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\n";
+            assert_eq!(
+                parse_cpu_model(x86).as_deref(),
+                Some("AMD Ryzen 7 5800X 8-Core Processor")
+            );
+            let arm = "processor\t: 0\nBogoMIPS\t: 108.00\n\nRevision\t: c03111\nModel\t\t: Raspberry Pi 4 Model B Rev 1.1\n";
+            assert_eq!(
+                parse_cpu_model(arm).as_deref(),
+                Some("Raspberry Pi 4 Model B Rev 1.1")
+            );
+            assert_eq!(parse_cpu_model(""), None);
+        }
+
+        #[test]
+        fn roundtrips_machines() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_machine": { "id": "abc", "cpu": null, "rustc": "rustc 1.95.0", "profile": "release" } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let machine = Machine {
+                id: "abc".into(),
+                cpu: None,
+                rustc: Some("rustc 1.95.0".into()),
+                profile: "release".into(),
+            };
+            assert_eq!(timings.data[0].machines, [Some(machine.clone()), None]);
+            assert_eq!(machine.summary(), "abc · rustc 1.95.0 · release");
+
+            let json = tinyjson::JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].machines, [Some(machine), None]);
+        }
     }
 }
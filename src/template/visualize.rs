@@ -0,0 +1,286 @@
+/// Terminal visualisations of solutions, e.g. of a grid after every round of a simulation.
+/// Frames are only built and shown when a solution runs with `--visualize`, and never while it is benchmarked.
+use std::{
+    fmt::{Display, Write as _},
+    fs::File,
+    io::{self, BufWriter, Write, stdout},
+    path::PathBuf,
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::template::ANSI_RESET;
+
+/// Frames per second when `--fps` is not passed.
+pub const DEFAULT_FPS: u32 = 10;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SINK: Mutex<Option<Sink>> = Mutex::new(None);
+
+/// How frames are shown, set by `--visualize`, `--fps` and `--frames`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Settings {
+    pub fps: u32,
+    /// Write the frames to this file instead of playing them.
+    pub frames: Option<PathBuf>,
+}
+
+/// Colours of the cells of a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+}
+
+impl Color {
+    fn ansi(self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Blue => "\x1b[34m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+            Color::Gray => "\x1b[90m",
+        }
+    }
+}
+
+/// A grid of characters with optional colours, and a caption shown below it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frame {
+    rows: Vec<Vec<(char, Option<Color>)>>,
+    caption: Option<String>,
+}
+
+impl Frame {
+    /// Creates a frame with a cell for every character of `text`, one row per line.
+    pub fn from_text(text: &str) -> Self {
+        Self {
+            rows: text
+                .lines()
+                .map(|line| line.chars().map(|c| (c, None)).collect())
+                .collect(),
+            caption: None,
+        }
+    }
+
+    /// Creates a frame of `width` by `height` cells, with the character of each cell returned by `cell(x, y)`.
+    pub fn from_fn(width: usize, height: usize, cell: impl Fn(usize, usize) -> char) -> Self {
+        Self {
+            rows: (0..height)
+                .map(|y| (0..width).map(|x| (cell(x, y), None)).collect())
+                .collect(),
+            caption: None,
+        }
+    }
+
+    /// Sets the character of a cell. Cells outside of the frame are ignored.
+    pub fn set(&mut self, x: usize, y: usize, c: char) -> &mut Self {
+        if let Some(cell) = self.rows.get_mut(y).and_then(|row| row.get_mut(x)) {
+            cell.0 = c;
+        }
+        self
+    }
+
+    /// Sets the colour of a cell. Cells outside of the frame are ignored.
+    pub fn color(&mut self, x: usize, y: usize, color: Color) -> &mut Self {
+        if let Some(cell) = self.rows.get_mut(y).and_then(|row| row.get_mut(x)) {
+            cell.1 = Some(color);
+        }
+        self
+    }
+
+    /// Colours every cell with the character `c`.
+    pub fn color_all(&mut self, c: char, color: Color) -> &mut Self {
+        for cell in self.rows.iter_mut().flatten().filter(|cell| cell.0 == c) {
+            cell.1 = Some(color);
+        }
+        self
+    }
+
+    /// Shows a line of text below the frame, e.g. the round of a simulation.
+    pub fn caption(&mut self, caption: impl Display) -> &mut Self {
+        self.caption = Some(caption.to_string());
+        self
+    }
+
+    /// Renders the frame without colours.
+    pub fn to_plain_string(&self) -> String {
+        let mut s = String::new();
+        for row in &self.rows {
+            s.extend(row.iter().map(|(c, _)| c));
+            s.push('\n');
+        }
+        if let Some(caption) = &self.caption {
+            let _ = writeln!(s, "{caption}");
+        }
+        s
+    }
+}
+
+impl Display for Frame {
+    /// Renders the frame with colours.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in &self.rows {
+            for (c, color) in row {
+                match color {
+                    Some(color) => write!(f, "{}{c}{ANSI_RESET}", color.ansi())?,
+                    None => write!(f, "{c}")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        if let Some(caption) = &self.caption {
+            writeln!(f, "{caption}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether frames are shown. Use it to skip work that is only needed for visualisations.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Shows the frame returned by `build`. `build` is only called when frames are shown.
+pub fn frame(build: impl FnOnce() -> Frame) {
+    if !enabled() {
+        return;
+    }
+
+    let frame = build();
+    if let Some(sink) = SINK.lock().unwrap_or_else(PoisonError::into_inner).as_mut() {
+        sink.show(&frame);
+    }
+}
+
+enum Output {
+    Terminal { delay: Duration },
+    File(BufWriter<File>),
+}
+
+struct Sink {
+    output: Output,
+    part: u8,
+    count: usize,
+    /// The first error writing frames, reported once the part has run.
+    error: Option<io::Error>,
+}
+
+impl Sink {
+    fn show(&mut self, frame: &Frame) {
+        self.count += 1;
+
+        let result = match &mut self.output {
+            Output::Terminal { delay } => {
+                // clear the screen and move the cursor to its top left corner.
+                let mut out = stdout().lock();
+                let result = write!(out, "\x1b[2J\x1b[H{frame}").and_then(|()| out.flush());
+                thread::sleep(*delay);
+                result
+            }
+            Output::File(file) => writeln!(
+                file,
+                "--- part {}, frame {} ---\n{}",
+                self.part,
+                self.count,
+                frame.to_plain_string()
+            ),
+        };
+
+        if let Err(e) = result {
+            self.error.get_or_insert(e);
+        }
+    }
+}
+
+/// Starts showing the frames of `part`. The frame file, if any, is created by the first part that runs.
+pub(crate) fn start(settings: &Settings, part: u8) -> io::Result<()> {
+    let mut sink = SINK.lock().unwrap_or_else(PoisonError::into_inner);
+    match sink.as_mut() {
+        Some(sink) => {
+            sink.part = part;
+            sink.count = 0;
+        }
+        None => {
+            let output = match &settings.frames {
+                Some(path) => Output::File(BufWriter::new(File::create(path)?)),
+                None => Output::Terminal {
+                    delay: Duration::from_secs(1) / settings.fps.max(1),
+                },
+            };
+            *sink = Some(Sink {
+                output,
+                part,
+                count: 0,
+                error: None,
+            });
+        }
+    }
+
+    ENABLED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Stops showing frames and returns how many frames the part showed.
+pub(crate) fn stop() -> io::Result<usize> {
+    ENABLED.store(false, Ordering::Relaxed);
+
+    let mut sink = SINK.lock().unwrap_or_else(PoisonError::into_inner);
+    let Some(sink) = sink.as_mut() else {
+        return Ok(0);
+    };
+    if let Output::File(file) = &mut sink.output {
+        file.flush()?;
+    }
+    match sink.error.take() {
+        Some(e) => Err(e),
+        None => Ok(sink.count),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Color, Frame};
+    use crate::template::ANSI_RESET;
+
+    #[test]
+    fn renders_frames() {
+        let mut frame = Frame::from_text("#.\n.#");
+        frame
+            .set(1, 0, '@')
+            .color(1, 0, Color::Red)
+            .caption("round 1");
+        frame.set(5, 5, 'x');
+
+        assert_eq!(frame.to_plain_string(), "#@\n.#\nround 1\n");
+        assert_eq!(
+            frame.to_string(),
+            format!("#\x1b[31m@{ANSI_RESET}\n.#\nround 1\n")
+        );
+    }
+
+    #[test]
+    fn builds_frames_from_functions() {
+        let mut frame = Frame::from_fn(3, 2, |x, y| if x == y { '#' } else { '.' });
+        frame.color_all('#', Color::Green);
+
+        assert_eq!(frame.to_plain_string(), "#..\n.#.\n");
+        assert_eq!(
+            frame.to_string(),
+            format!("\x1b[32m#{ANSI_RESET}..\n.\x1b[32m#{ANSI_RESET}.\n")
+        );
+    }
+}